@@ -28,6 +28,60 @@ pub trait AsyncReadExt: AsyncRead {
     {
         future::poll_fn(move |ctx| Pin::new(&mut *self).poll_read(ctx, buf))
     }
+
+    /// Reads exactly enough bytes to fill `buf`.
+    ///
+    /// Fails with [`io::ErrorKind::UnexpectedEof`] if the source is exhausted
+    /// before `buf` is filled.
+    fn read_exact<'a>(
+        &'a mut self,
+        mut buf: &'a mut [u8],
+    ) -> impl Future<Output = io::Result<()>> + 'a
+    where
+        Self: std::marker::Unpin,
+    {
+        async move {
+            while !buf.is_empty() {
+                let n = self.read(buf).await?;
+
+                if n == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ));
+                }
+
+                buf = &mut buf[n..];
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Reads all remaining bytes, appending them to `buf` until EOF, and
+    /// returns the number of bytes read.
+    fn read_to_end<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+    ) -> impl Future<Output = io::Result<usize>> + 'a
+    where
+        Self: std::marker::Unpin,
+    {
+        async move {
+            let start = buf.len();
+            let mut scratch = [0u8; 4096];
+
+            loop {
+                let n = self.read(&mut scratch).await?;
+
+                if n == 0 {
+                    return Ok(buf.len() - start);
+                }
+
+                buf.extend_from_slice(&scratch[..n]);
+            }
+        }
+    }
 }
 
 impl<T: AsyncRead + ?Sized> AsyncReadExt for T {}
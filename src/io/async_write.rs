@@ -23,6 +23,26 @@ pub trait AsyncWrite {
 
     /// Shuts down the write half of this object.
     fn poll_shutdown(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Attempts to write bytes from `bufs` into this writer using vectored
+    /// I/O, returning the number of bytes written.
+    ///
+    /// The default implementation writes only the first non-empty slice
+    /// through [`poll_write`](AsyncWrite::poll_write); implementors backed by
+    /// a single file descriptor should override this to issue one `writev(2)`
+    /// across every slice instead.
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let buf = bufs
+            .iter()
+            .find(|buf| !buf.is_empty())
+            .map_or(&[][..], |buf| &**buf);
+
+        self.poll_write(ctx, buf)
+    }
 }
 
 /// Implemented as an extension trait, adding utility methods to `AsyncWrite`
@@ -60,6 +80,46 @@ pub trait AsyncWriteExt: AsyncWrite {
         }
     }
 
+    /// Attempts to write bytes from `bufs` into this writer using vectored
+    /// I/O, returning the number of bytes written.
+    fn write_vectored<'a>(
+        &'a mut self,
+        bufs: &'a [io::IoSlice<'a>],
+    ) -> impl Future<Output = io::Result<usize>> + 'a
+    where
+        Self: std::marker::Unpin,
+    {
+        future::poll_fn(move |ctx| Pin::new(&mut *self).poll_write_vectored(ctx, bufs))
+    }
+
+    /// Writes an entire list of `bufs` into this writer, advancing across
+    /// slices as partial writes occur.
+    fn write_all_vectored<'a>(
+        &'a mut self,
+        mut bufs: &'a mut [io::IoSlice<'a>],
+    ) -> impl Future<Output = io::Result<()>> + 'a
+    where
+        Self: std::marker::Unpin,
+    {
+        async move {
+            while !bufs.is_empty() {
+                let n = future::poll_fn(|ctx| Pin::new(&mut *self).poll_write_vectored(ctx, bufs))
+                    .await?;
+
+                if n == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write all bytes",
+                    ));
+                }
+
+                io::IoSlice::advance_slices(&mut bufs, n);
+            }
+
+            Ok(())
+        }
+    }
+
     /// Flushes any buffered data.
     fn flush<'a>(&'a mut self) -> impl Future<Output = io::Result<()>> + 'a
     where
@@ -5,3 +5,6 @@ pub use async_read::{AsyncRead, AsyncReadExt};
 
 mod async_write;
 pub use async_write::{AsyncWrite, AsyncWriteExt};
+
+mod rate_limited;
+pub use rate_limited::RateLimited;
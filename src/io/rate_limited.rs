@@ -0,0 +1,282 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use crate::io::{AsyncRead, AsyncWrite};
+use crate::rt::timer::TimerId;
+use crate::rt::CURRENT_RUNTIME;
+
+/// Throttles throughput on an `AsyncRead`/`AsyncWrite` type using a token
+/// bucket, giving proxies and test harnesses bandwidth shaping without a
+/// separate crate.
+///
+/// One token permits one byte transferred. The bucket refills continuously at
+/// `refill_rate` bytes/second up to `capacity`, so bursts up to `capacity`
+/// bytes are allowed before throttling kicks in.
+#[derive(Debug)]
+pub struct RateLimited<T> {
+    inner: T,
+    /// Bytes currently available to spend. Starts full (`capacity`).
+    tokens: f64,
+    /// Maximum number of tokens the bucket can hold (the burst size).
+    capacity: f64,
+    /// Bytes/second the bucket refills at.
+    refill_rate: f64,
+    /// Last time `tokens` was refilled.
+    last_refill: Instant,
+    /// Indicates whether a wake-up timer is already registered for this task,
+    /// so a run of `Poll::Pending`s doesn't pile up redundant timers.
+    registered: bool,
+    /// Handle to the registered timer, so it can be cancelled if
+    /// `RateLimited` is dropped before it fires.
+    timer_id: Option<TimerId>,
+}
+
+impl<T> RateLimited<T> {
+    /// Wraps `inner`, limiting it to `rate` bytes/second with bursts of up to
+    /// `capacity` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not a positive, finite number. A rate of zero
+    /// (or less) can't be expressed as a finite refill wait, which a
+    /// throttled-but-not-paused `RateLimited` depends on to schedule its
+    /// wake-up.
+    ///
+    /// Panics if `capacity` is not a positive, finite number. A non-positive
+    /// ceiling means `tokens` can never reach `1.0`, so every `poll_read`/
+    /// `poll_write` would block forever.
+    pub fn new(inner: T, rate: f64, capacity: f64) -> Self {
+        assert!(
+            rate.is_finite() && rate > 0.0,
+            "RateLimited rate must be a positive, finite number of bytes/second, got {rate}"
+        );
+        assert!(
+            capacity.is_finite() && capacity > 0.0,
+            "RateLimited capacity must be a positive, finite number of bytes, got {capacity}"
+        );
+
+        RateLimited {
+            inner,
+            tokens: capacity,
+            capacity,
+            refill_rate: rate,
+            last_refill: Instant::now(),
+            registered: false,
+            timer_id: None,
+        }
+    }
+
+    /// Returns a shared reference to the wrapped value.
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Refills `tokens` based on the time elapsed since the last refill,
+    /// capped at `capacity`.
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill);
+
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.refill_rate).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.registered = false;
+
+            // Tokens only accumulate with time, so the timer's deadline (the
+            // moment the first token is back) has passed and the wheel may
+            // already have fired it and reused its slot; forget the id
+            // rather than risk cancelling some other timer.
+            self.timer_id = None;
+        }
+    }
+
+    /// Registers a timer to wake the current task once at least one token is
+    /// available, unless one is already pending.
+    fn schedule_wake(&mut self) {
+        if self.registered {
+            return;
+        }
+        self.registered = true;
+
+        let tokens_needed = 1.0 - self.tokens;
+        let wait = Duration::from_secs_f64(tokens_needed / self.refill_rate);
+        let wake_at = Instant::now() + wait;
+
+        self.timer_id = CURRENT_RUNTIME.with(|rt| {
+            if let Some(ptr) = rt.get() {
+                // SAFETY: The thread-local holds a raw pointer to a
+                // `Runtime`. This pointer is only set via the entry point
+                // `Runtime::block_on`, and cleared when the associated
+                // `EnterGuard` is dropped. Polling a `RateLimited` is only
+                // possible within the context of a runtime.
+                let rt = unsafe { &*ptr };
+                rt.scheduler.register_timer(wake_at)
+            } else {
+                panic!("`RateLimited` polled outside of a rutime context");
+            }
+        });
+    }
+}
+
+impl<T> Drop for RateLimited<T> {
+    fn drop(&mut self) {
+        // Forgets the id of a timer whose deadline has passed, see `refill`.
+        self.refill();
+
+        if let Some(id) = self.timer_id.take() {
+            CURRENT_RUNTIME.with(|rt| {
+                if let Some(ptr) = rt.get() {
+                    // SAFETY: See the comment in `schedule_wake`.
+                    let rt = unsafe { &*ptr };
+                    rt.scheduler.cancel_timer(id);
+                }
+            });
+        }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for RateLimited<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.refill();
+
+        if this.tokens < 1.0 {
+            this.schedule_wake();
+            return Poll::Pending;
+        }
+
+        let limit = (this.tokens.floor() as usize).min(buf.len());
+
+        match Pin::new(&mut this.inner).poll_read(ctx, &mut buf[..limit]) {
+            Poll::Ready(Ok(n)) => {
+                this.tokens -= n as f64;
+                Poll::Ready(Ok(n))
+            }
+            other => other,
+        }
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for RateLimited<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.refill();
+
+        if this.tokens < 1.0 {
+            this.schedule_wake();
+            return Poll::Pending;
+        }
+
+        let limit = (this.tokens.floor() as usize).min(buf.len());
+
+        match Pin::new(&mut this.inner).poll_write(ctx, &buf[..limit]) {
+            Poll::Ready(Ok(n)) => {
+                this.tokens -= n as f64;
+                Poll::Ready(Ok(n))
+            }
+            other => other,
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(ctx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{AsyncReadExt, AsyncWriteExt};
+    use crate::rt::Runtime;
+
+    use super::*;
+
+    /// Test double that completes every read/write immediately, so these
+    /// tests exercise `RateLimited`'s token-bucket math rather than any real
+    /// I/O.
+    struct AlwaysReady;
+
+    impl AsyncRead for AlwaysReady {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(Ok(buf.len()))
+        }
+    }
+
+    impl AsyncWrite for AlwaysReady {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "rate must be a positive, finite number")]
+    fn new_panics_on_non_positive_rate() {
+        RateLimited::new(AlwaysReady, 0.0, 10.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be a positive, finite number")]
+    fn new_panics_on_non_positive_capacity() {
+        RateLimited::new(AlwaysReady, 10.0, 0.0);
+    }
+
+    #[test]
+    fn burst_up_to_capacity_succeeds_without_waiting() {
+        Runtime::new().block_on(async {
+            let mut limited = RateLimited::new(AlwaysReady, 100.0, 10.0);
+            let mut buf = [0u8; 10];
+
+            // The bucket starts full, so a read up to `capacity` bytes
+            // should go through without throttling.
+            assert_eq!(limited.read(&mut buf).await.unwrap(), 10);
+        });
+    }
+
+    #[test]
+    fn exhausted_tokens_throttle_until_refilled() {
+        Runtime::new().block_on(async {
+            // A tiny bucket with a fast refill so the test doesn't have to
+            // wait long for the second read to unblock.
+            let mut limited = RateLimited::new(AlwaysReady, 1_000.0, 1.0);
+            let buf = [0u8; 1];
+
+            // Drains the only token in the bucket.
+            assert_eq!(limited.write(&buf).await.unwrap(), 1);
+
+            // No tokens left: this has to wait on the refill timer instead
+            // of busy-looping, but must still eventually resolve.
+            assert_eq!(limited.write(&buf).await.unwrap(), 1);
+        });
+    }
+}
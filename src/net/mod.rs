@@ -1,6 +1,12 @@
 //! Networking bindings for `rio`.
 
 mod tcp;
-pub use tcp::{TcpListener, TcpStream};
+pub use tcp::{OwnedReadHalf, OwnedWriteHalf, ReadHalf, TcpListener, TcpStream, WriteHalf};
+
+mod udp;
+pub use udp::UdpSocket;
+
+mod unix;
+pub use unix::{UnixDatagram, UnixListener, UnixStream};
 
 pub(crate) mod socket;
@@ -1,15 +1,111 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::future::Future;
-use std::net::SocketAddr;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::os::fd::FromRawFd;
 use std::os::unix::io::RawFd;
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::task::{Context, Poll, Waker};
 use std::{io, mem, ptr};
 
 use crate::rt::Runtime;
 use crate::rt::io::errno;
 
+/// Converts a `SocketAddr` into its raw `sockaddr_storage` representation,
+/// returning the storage along with the length of the `sockaddr_in`/
+/// `sockaddr_in6` written into it.
+pub(crate) fn to_sockaddr_storage(addr: SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    let mut sock_addr_s: libc::sockaddr_storage = unsafe { mem::zeroed() };
+
+    let sock_len = match addr {
+        SocketAddr::V4(v4) => {
+            let ipv4 = libc::sockaddr_in {
+                sin_family: libc::AF_INET as u16,
+                sin_port: v4.port().to_be(), // network-byte order
+                sin_addr: libc::in_addr {
+                    // Already in network-byte order.
+                    s_addr: u32::from_ne_bytes(v4.ip().octets()),
+                },
+                sin_zero: [0; 8],
+            };
+
+            unsafe {
+                ptr::write(&raw mut sock_addr_s as *mut libc::sockaddr_in, ipv4);
+            }
+
+            mem::size_of_val(&ipv4) as libc::socklen_t
+        }
+        SocketAddr::V6(v6) => {
+            let ipv6 = libc::sockaddr_in6 {
+                sin6_family: libc::AF_INET6 as u16,
+                sin6_port: v6.port().to_be(), // network-byte order
+                sin6_flowinfo: v6.flowinfo(),
+                sin6_addr: libc::in6_addr {
+                    s6_addr: v6.ip().octets(),
+                },
+                sin6_scope_id: v6.scope_id(),
+            };
+
+            unsafe {
+                ptr::write(&raw mut sock_addr_s as *mut libc::sockaddr_in6, ipv6);
+            }
+
+            mem::size_of_val(&ipv6) as libc::socklen_t
+        }
+    };
+
+    (sock_addr_s, sock_len)
+}
+
+/// Parses a `sockaddr_storage` populated by a `recvfrom(2)`/`getsockname(2)`
+/// style call back into a Rust `SocketAddr`.
+pub(crate) fn from_sockaddr_storage(storage: &libc::sockaddr_storage) -> io::Result<SocketAddr> {
+    match storage.ss_family as libc::c_int {
+        libc::AF_INET => {
+            // SAFETY: `ss_family` indicates `storage` was populated as a
+            // `sockaddr_in`, which `sockaddr_storage` is large enough to hold.
+            let addr_in = unsafe { &*(storage as *const _ as *const libc::sockaddr_in) };
+
+            let ip = Ipv4Addr::from(addr_in.sin_addr.s_addr.to_ne_bytes());
+            let port = u16::from_be(addr_in.sin_port);
+
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+        }
+        libc::AF_INET6 => {
+            // SAFETY: `ss_family` indicates `storage` was populated as a
+            // `sockaddr_in6`, which `sockaddr_storage` is large enough to
+            // hold.
+            let addr_in6 = unsafe { &*(storage as *const _ as *const libc::sockaddr_in6) };
+
+            let ip = Ipv6Addr::from(addr_in6.sin6_addr.s6_addr);
+            let port = u16::from_be(addr_in6.sin6_port);
+
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                port,
+                addr_in6.sin6_flowinfo,
+                addr_in6.sin6_scope_id,
+            )))
+        }
+        family => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported address family: {family}"),
+        )),
+    }
+}
+
+/// Creates a non-blocking socket of the given address `family` (`AF_INET`/
+/// `AF_INET6`) and `kind` (`SOCK_STREAM`/`SOCK_DGRAM`).
+pub(crate) fn new_nonblocking_socket(family: libc::c_int, kind: libc::c_int) -> io::Result<RawFd> {
+    unsafe {
+        let raw_fd = libc::socket(family, kind | libc::SOCK_NONBLOCK, 0);
+        if raw_fd == -1 {
+            return Err(errno!("failed to create non-blocking socket"));
+        }
+
+        Ok(raw_fd)
+    }
+}
+
 /// Raw, non-blocking socket used for initiating outbound TCP connections.
 pub(crate) struct TcpSocket {
     fd: RawFd,
@@ -17,68 +113,25 @@ pub(crate) struct TcpSocket {
     sock_len: libc::socklen_t,
     /// Indicates whether a connection was established using this socket.
     connected: Cell<bool>,
+    /// `Waker` most recently registered for write-readiness by `ConnectFut`,
+    /// so it can be deregistered if the future is dropped before the
+    /// connection completes, instead of leaking a stale entry in the
+    /// driver's waker list.
+    write_waker: RefCell<Option<Waker>>,
 }
 
 impl TcpSocket {
     /// Creates a new non-blocking `TcpSocket` with a specified remote address.
     pub(crate) fn new(addr: SocketAddr) -> io::Result<Self> {
-        let mut sock_addr_s: libc::sockaddr_storage = unsafe { mem::zeroed() };
-
-        let sock_len = match addr {
-            SocketAddr::V4(v4) => {
-                let ipv4 = libc::sockaddr_in {
-                    sin_family: libc::AF_INET as u16,
-                    sin_port: v4.port().to_be(), // network-byte order
-                    sin_addr: libc::in_addr {
-                        // Already in network-byte order.
-                        s_addr: u32::from_ne_bytes(v4.ip().octets()),
-                    },
-                    sin_zero: [0; 8],
-                };
-
-                unsafe {
-                    ptr::write(&raw mut sock_addr_s as *mut libc::sockaddr_in, ipv4);
-                }
-
-                mem::size_of_val(&ipv4) as libc::socklen_t
-            }
-            SocketAddr::V6(v6) => {
-                let ipv6 = libc::sockaddr_in6 {
-                    sin6_family: libc::AF_INET6 as u16,
-                    sin6_port: v6.port().to_be(), // network-byte order
-                    sin6_flowinfo: v6.flowinfo(),
-                    sin6_addr: libc::in6_addr {
-                        s6_addr: v6.ip().octets(),
-                    },
-                    sin6_scope_id: v6.scope_id(),
-                };
-
-                unsafe {
-                    ptr::write(&raw mut sock_addr_s as *mut libc::sockaddr_in6, ipv6);
-                }
-
-                mem::size_of_val(&ipv6) as libc::socklen_t
-            }
-        };
-
-        let fd = unsafe {
-            let raw_fd = libc::socket(
-                sock_addr_s.ss_family as libc::c_int,
-                libc::SOCK_STREAM | libc::SOCK_NONBLOCK,
-                0,
-            );
-            if raw_fd == -1 {
-                return Err(errno!("failed to created non-blocking TcpSocket"));
-            }
-
-            raw_fd
-        };
+        let (sock_addr_s, sock_len) = to_sockaddr_storage(addr);
+        let fd = new_nonblocking_socket(sock_addr_s.ss_family as libc::c_int, libc::SOCK_STREAM)?;
 
         Ok(TcpSocket {
-            fd: RawFd::from(fd),
+            fd,
             sock_addr_s,
             sock_len,
             connected: Cell::new(false),
+            write_waker: RefCell::new(None),
         })
     }
 
@@ -91,8 +144,20 @@ impl TcpSocket {
 }
 
 impl Drop for TcpSocket {
+    // SAFETY: The current runtime is guaranteed to be set via thread-local
+    // storage when entering `Runtime::block_on`, which is the only entry
+    // point for asynchronous execution, therefore, any async code, including
+    // this `Drop`, must be running within a valid runtime context to be
+    // called.
     fn drop(&mut self) {
         if !self.connected.get() {
+            // The connection never completed (the `TcpSocket` was dropped, or
+            // the `ConnectFut` borrowing it was cancelled mid-flight), so this
+            // fd will never be handed off to a `TcpStream` to unregister on
+            // its own `Drop` — do it here instead, otherwise the driver keeps
+            // a phantom `ScheduledIo` entry around forever.
+            Runtime::current().scheduler.unregister_fd(self.fd);
+
             unsafe {
                 libc::close(self.fd);
             }
@@ -120,11 +185,12 @@ impl Future for ConnectFut<'_> {
                         // socket becomes writable once the connection is
                         // established.
                         let events = libc::EPOLLOUT;
-                        Runtime::current().scheduler.register_fd(
-                            self.0.fd,
-                            events as u32,
-                            ctx.waker().clone(),
-                        );
+                        let waker = ctx.waker().clone();
+
+                        Runtime::current()
+                            .scheduler
+                            .register_fd(self.0.fd, events as u32, waker.clone());
+                        *self.0.write_waker.borrow_mut() = Some(waker);
 
                         return Poll::Pending;
                     }
@@ -149,3 +215,26 @@ impl Future for ConnectFut<'_> {
         }
     }
 }
+
+impl Drop for ConnectFut<'_> {
+    // SAFETY: The current runtime is guaranteed to be set via thread-local
+    // storage when entering `Runtime::block_on`, which is the only entry
+    // point for asynchronous execution, therefore, any async code, including
+    // this `Drop`, must be running within a valid runtime context to be
+    // called.
+    fn drop(&mut self) {
+        // If the connection already completed, the registration belongs to
+        // the `TcpStream` that now owns the fd, not to this future.
+        if self.0.connected.get() {
+            return;
+        }
+
+        if let Some(waker) = self.0.write_waker.borrow_mut().take() {
+            Runtime::current().scheduler.deregister_waker(
+                self.0.fd,
+                libc::EPOLLOUT as u32,
+                &waker,
+            );
+        }
+    }
+}
@@ -1,22 +1,23 @@
 use std::io::{self, Read, Write};
 use std::net::SocketAddr;
 use std::net::ToSocketAddrs;
-use std::os::unix::io::AsRawFd;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::pin::Pin;
+use std::rc::Rc;
 use std::task::Context;
 use std::task::Poll;
 
 use crate::io::AsyncRead;
 use crate::io::AsyncWrite;
 use crate::net::socket::TcpSocket;
-use crate::rt::Runtime;
+use crate::rt::io::PollEvented;
 
 /// A TCP stream between a local and a remote socket.
 ///
 /// Reading and writing to a TcpStream is usually done using the methods found
 /// on the `AsyncRead` and `AsyncWrite` traits.
 #[derive(Debug)]
-pub struct TcpStream(std::net::TcpStream);
+pub struct TcpStream(PollEvented<std::net::TcpStream>);
 
 impl TcpStream {
     /// Opens a TCP connection to a remote host.
@@ -53,18 +54,18 @@ impl TcpStream {
     /// Returns the socket address of the local half of this TCP connection.
     #[inline]
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
-        self.0.local_addr()
+        self.0.get_ref().local_addr()
     }
 
     /// Returns the socket address of the remote peer of this TCP connection.
     #[inline]
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
-        self.0.peer_addr()
+        self.0.get_ref().peer_addr()
     }
 
     /// Gets the value of the `IP_TTL` option for this socket.
     pub fn ttl(&self) -> io::Result<u32> {
-        self.0.ttl()
+        self.0.get_ref().ttl()
     }
 
     /// Sets the value for the `IP_TTL` option on this socket.
@@ -72,7 +73,32 @@ impl TcpStream {
     /// This value sets the time-to-live field that is used in every packet sent
     /// from this socket.
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
-        self.0.set_ttl(ttl)
+        self.0.get_ref().set_ttl(ttl)
+    }
+
+    /// Splits this `TcpStream` by reference into a read half and a write
+    /// half that can be used (e.g. `select`ed on) concurrently, without
+    /// allocating.
+    ///
+    /// Since both halves borrow `self`, they cannot outlive it or be moved
+    /// into separate spawned tasks; use [`into_split`] for that.
+    ///
+    /// [`into_split`]: TcpStream::into_split
+    pub fn split(&mut self) -> (ReadHalf<'_>, WriteHalf<'_>) {
+        (ReadHalf(&self.0), WriteHalf(&self.0))
+    }
+
+    /// Splits this `TcpStream` into an owned read half and an owned write
+    /// half that can each be moved independently, e.g. into their own spawned
+    /// task.
+    ///
+    /// The two halves share the underlying socket via `Rc`, relying on the
+    /// I/O driver's per-direction readiness tracking so reading and writing
+    /// can be awaited concurrently without one half's registration clobbering
+    /// the other's. The socket is closed once both halves have been dropped.
+    pub fn into_split(self) -> (OwnedReadHalf, OwnedWriteHalf) {
+        let io = Rc::new(self.0);
+        (OwnedReadHalf(Rc::clone(&io)), OwnedWriteHalf(io))
     }
 
     /// Establishes a connection to the specified `addr`.
@@ -87,75 +113,36 @@ impl TryFrom<std::net::TcpStream> for TcpStream {
     type Error = io::Error;
 
     fn try_from(stream: std::net::TcpStream) -> Result<Self, Self::Error> {
-        // Required to make sure `stream` can be polled without blocking when
-        // awaited.
-        stream.set_nonblocking(true)?;
-        Ok(TcpStream(stream))
+        // `PollEvented::new` sets the fd non-blocking so `stream` can be
+        // polled without blocking when awaited, and unregisters it from the
+        // I/O driver on drop.
+        Ok(TcpStream(PollEvented::new(stream)?))
     }
 }
 
-impl Drop for TcpStream {
-    fn drop(&mut self) {
-        // SAFETY: The current runtime is guaranteed to be set via thread-local
-        // storage when entering `Runtime::block_on`, which is the only entry
-        // point for asynchronous execution, therefore, any async code,
-        // including this `Drop`, must be running within a valid runtime context
-        // to be called.
-        Runtime::current()
-            .scheduler
-            .unregister_fd(self.0.as_raw_fd());
-
-        // Inner `std::net::TcpStream` is dropped...
+impl AsRawFd for TcpStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
     }
 }
 
 impl AsyncRead for TcpStream {
     fn poll_read(
-        mut self: Pin<&mut Self>,
+        self: Pin<&mut Self>,
         ctx: &mut Context<'_>,
         buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
-        match self.0.read(buf) {
-            Ok(rbytes) => Poll::Ready(Ok(rbytes)),
-            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                // Register for read readiness notifications.
-                let events = libc::EPOLLIN;
-
-                Runtime::current().scheduler.register_fd(
-                    self.0.as_raw_fd(),
-                    events as u32,
-                    ctx.waker().clone(),
-                );
-
-                Poll::Pending
-            }
-            Err(e) => Poll::Ready(Err(e)),
-        }
+        poll_read(&self.0, ctx, buf)
     }
 }
 
 impl AsyncWrite for TcpStream {
     fn poll_write(
-        mut self: Pin<&mut Self>,
+        self: Pin<&mut Self>,
         ctx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
-        match self.0.write(buf) {
-            Ok(wbytes) => Poll::Ready(Ok(wbytes)),
-            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                // Register for write readiness notifications.
-                let events = libc::EPOLLOUT;
-
-                Runtime::current().scheduler.register_fd(
-                    self.0.as_raw_fd(),
-                    events as u32,
-                    ctx.waker().clone(),
-                );
-
-                Poll::Pending
-            }
-            Err(e) => Poll::Ready(Err(e)),
-        }
+        poll_write(&self.0, ctx, buf)
     }
 
     fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
@@ -163,22 +150,209 @@ impl AsyncWrite for TcpStream {
     }
 
     fn poll_shutdown(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        match self.0.shutdown(std::net::Shutdown::Write) {
-            Ok(()) => Poll::Ready(Ok(())),
-            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                // Register for write readiness notifications, so shutdown can
-                // be retried.
-                let events = libc::EPOLLOUT;
-
-                Runtime::current().scheduler.register_fd(
-                    self.0.as_raw_fd(),
-                    events as u32,
-                    ctx.waker().clone(),
-                );
-
-                Poll::Pending
-            }
-            Err(e) => Poll::Ready(Err(e)),
-        }
+        poll_shutdown(&self.0, ctx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_vectored(&self.0, ctx, bufs)
+    }
+}
+
+/// Borrowed read half of a [`TcpStream`], created by [`TcpStream::split`].
+#[derive(Debug)]
+pub struct ReadHalf<'a>(&'a PollEvented<std::net::TcpStream>);
+
+impl AsyncRead for ReadHalf<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_read(self.0, ctx, buf)
+    }
+}
+
+/// Borrowed write half of a [`TcpStream`], created by [`TcpStream::split`].
+#[derive(Debug)]
+pub struct WriteHalf<'a>(&'a PollEvented<std::net::TcpStream>);
+
+impl AsyncWrite for WriteHalf<'_> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_write(self.0, ctx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        poll_shutdown(self.0, ctx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_vectored(self.0, ctx, bufs)
+    }
+}
+
+/// Owned read half of a [`TcpStream`], created by [`TcpStream::into_split`].
+///
+/// Shares the underlying socket with its corresponding [`OwnedWriteHalf`] via
+/// `Rc`; the socket is closed once both halves are dropped.
+#[derive(Debug)]
+pub struct OwnedReadHalf(Rc<PollEvented<std::net::TcpStream>>);
+
+impl AsyncRead for OwnedReadHalf {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_read(&self.0, ctx, buf)
+    }
+}
+
+/// Owned write half of a [`TcpStream`], created by [`TcpStream::into_split`].
+///
+/// Shares the underlying socket with its corresponding [`OwnedReadHalf`] via
+/// `Rc`; the socket is closed once both halves are dropped.
+#[derive(Debug)]
+pub struct OwnedWriteHalf(Rc<PollEvented<std::net::TcpStream>>);
+
+impl AsyncWrite for OwnedWriteHalf {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_write(&self.0, ctx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        poll_shutdown(&self.0, ctx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_vectored(&self.0, ctx, bufs)
+    }
+}
+
+/// Runs a buffered read against `io`, registering for the next `EPOLLIN`-class
+/// edge on `WouldBlock`. Shared by `TcpStream` and both its split halves.
+fn poll_read(
+    io: &PollEvented<std::net::TcpStream>,
+    ctx: &mut Context<'_>,
+    buf: &mut [u8],
+) -> Poll<io::Result<usize>> {
+    io.poll_read_with(ctx, |stream| {
+        let mut stream = stream;
+        stream.read(buf)
+    })
+}
+
+/// Runs a buffered write against `io`, registering for the next `EPOLLOUT`
+/// edge on `WouldBlock`. Shared by `TcpStream` and both its split halves.
+fn poll_write(
+    io: &PollEvented<std::net::TcpStream>,
+    ctx: &mut Context<'_>,
+    buf: &[u8],
+) -> Poll<io::Result<usize>> {
+    io.poll_write_with(ctx, |stream| {
+        let mut stream = stream;
+        stream.write(buf)
+    })
+}
+
+/// Half-closes the write direction of `io`. Shared by `TcpStream` and both of
+/// its write halves.
+fn poll_shutdown(
+    io: &PollEvented<std::net::TcpStream>,
+    ctx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    io.poll_write_with(ctx, |stream| stream.shutdown(std::net::Shutdown::Write))
+}
+
+/// Runs a single `writev(2)` across `bufs` against `io`, registering for the
+/// next `EPOLLOUT` edge on `WouldBlock`. Shared by `TcpStream` and both its
+/// write halves.
+fn poll_write_vectored(
+    io: &PollEvented<std::net::TcpStream>,
+    ctx: &mut Context<'_>,
+    bufs: &[io::IoSlice<'_>],
+) -> Poll<io::Result<usize>> {
+    io.poll_write_with(ctx, |stream| {
+        let mut stream = stream;
+        stream.write_vectored(bufs)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::rt::Runtime;
+
+    /// Connects a loopback pair of plain `std::net::TcpStream`s, without
+    /// going through our own `connect`/`TcpListener`.
+    fn loopback_pair() -> (std::net::TcpStream, std::net::TcpStream) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = std::net::TcpStream::connect(addr).unwrap();
+        let (server, _) = listener.accept().unwrap();
+
+        (client, server)
+    }
+
+    #[test]
+    fn into_split_shares_ownership_via_rc() {
+        // `AsyncFd`'s `Drop` unregisters from the runtime's I/O driver, so
+        // both halves must be dropped inside a runtime context.
+        Runtime::new().block_on(async {
+            let (_client, server) = loopback_pair();
+            let stream = TcpStream::try_from(server).unwrap();
+
+            let (read_half, write_half) = stream.into_split();
+
+            // Both halves hold a clone of the same `Rc`, so the socket stays
+            // open as long as either is alive.
+            assert_eq!(Rc::strong_count(&read_half.0), 2);
+            assert_eq!(Rc::strong_count(&write_half.0), 2);
+
+            drop(read_half);
+            assert_eq!(Rc::strong_count(&write_half.0), 1);
+        });
+    }
+
+    #[test]
+    fn split_borrows_the_same_underlying_socket() {
+        Runtime::new().block_on(async {
+            let (_client, server) = loopback_pair();
+            let mut stream = TcpStream::try_from(server).unwrap();
+
+            let (read_half, write_half) = stream.split();
+
+            assert!(std::ptr::eq(read_half.0, write_half.0));
+        });
     }
 }
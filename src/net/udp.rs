@@ -0,0 +1,437 @@
+use std::future;
+use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::task::{Context, Poll};
+use std::{fmt, io};
+
+use crate::net::socket::{from_sockaddr_storage, new_nonblocking_socket, to_sockaddr_storage};
+use crate::rt::io::{errno, PollEvented};
+
+/// A UDP socket.
+///
+/// After creating a `UdpSocket` by `bind`ing it to a local address, data can
+/// be sent to and received from any remote address with `send_to`/
+/// `recv_from`. A `UdpSocket` may also be `connect`ed to a single remote
+/// address, after which `send`/`recv` can be used instead.
+pub struct UdpSocket {
+    io: PollEvented<RawUdpSocket>,
+}
+
+impl UdpSocket {
+    /// Creates a UDP socket bound to the specified address.
+    pub async fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let addr = resolve_one(addr)?;
+        let (sock_addr_s, sock_len) = to_sockaddr_storage(addr);
+        let fd = new_nonblocking_socket(sock_addr_s.ss_family as libc::c_int, libc::SOCK_DGRAM)?;
+
+        if unsafe { libc::bind(fd, &raw const sock_addr_s as *const libc::sockaddr, sock_len) }
+            == -1
+        {
+            let err = errno!("failed to bind UDP socket");
+            unsafe { libc::close(fd) };
+            return Err(err);
+        }
+
+        Ok(UdpSocket {
+            io: PollEvented::new(RawUdpSocket(fd))?,
+        })
+    }
+
+    /// Connects this UDP socket to a remote address, restricting `send`/
+    /// `recv` to that peer.
+    ///
+    /// Unlike TCP, this does not perform a handshake; it only records the
+    /// default destination for the socket.
+    pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> io::Result<()> {
+        let addr = resolve_one(addr)?;
+        let (sock_addr_s, sock_len) = to_sockaddr_storage(addr);
+
+        if unsafe {
+            libc::connect(
+                self.io.as_raw_fd(),
+                &raw const sock_addr_s as *const libc::sockaddr,
+                sock_len,
+            )
+        } == -1
+        {
+            return Err(errno!("failed to connect UDP socket"));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the local address that this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        let mut sock_addr_s: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut sock_len = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+        if unsafe {
+            libc::getsockname(
+                self.io.as_raw_fd(),
+                &raw mut sock_addr_s as *mut libc::sockaddr,
+                &raw mut sock_len,
+            )
+        } == -1
+        {
+            return Err(errno!("failed to get local address"));
+        }
+
+        from_sockaddr_storage(&sock_addr_s)
+    }
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        let mut ttl: libc::c_int = 0;
+        let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+
+        if unsafe {
+            libc::getsockopt(
+                self.io.as_raw_fd(),
+                libc::IPPROTO_IP,
+                libc::IP_TTL,
+                (&raw mut ttl).cast(),
+                &raw mut len,
+            )
+        } == -1
+        {
+            return Err(errno!("failed to get IP_TTL"));
+        }
+
+        Ok(ttl as u32)
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    ///
+    /// This value sets the time-to-live field that is used in every packet
+    /// sent from this socket.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        let ttl = ttl as libc::c_int;
+
+        if unsafe {
+            libc::setsockopt(
+                self.io.as_raw_fd(),
+                libc::IPPROTO_IP,
+                libc::IP_TTL,
+                (&raw const ttl).cast(),
+                mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        } == -1
+        {
+            return Err(errno!("failed to set IP_TTL"));
+        }
+
+        Ok(())
+    }
+
+    /// Gets the value of the `SO_BROADCAST` option for this socket.
+    pub fn broadcast(&self) -> io::Result<bool> {
+        let mut on: libc::c_int = 0;
+        let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+
+        if unsafe {
+            libc::getsockopt(
+                self.io.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_BROADCAST,
+                (&raw mut on).cast(),
+                &raw mut len,
+            )
+        } == -1
+        {
+            return Err(errno!("failed to get SO_BROADCAST"));
+        }
+
+        Ok(on != 0)
+    }
+
+    /// Sets the value for the `SO_BROADCAST` option on this socket.
+    ///
+    /// When enabled, this socket is allowed to send packets to a broadcast
+    /// address.
+    pub fn set_broadcast(&self, on: bool) -> io::Result<()> {
+        let on: libc::c_int = on as libc::c_int;
+
+        if unsafe {
+            libc::setsockopt(
+                self.io.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_BROADCAST,
+                (&raw const on).cast(),
+                mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        } == -1
+        {
+            return Err(errno!("failed to set SO_BROADCAST"));
+        }
+
+        Ok(())
+    }
+
+    /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
+    ///
+    /// This function specifies a new multicast group for this socket to join.
+    /// The address must be a valid multicast address, and `interface` is the
+    /// address of the local interface with which the system should join the
+    /// multicast group.
+    pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        let mreq = libc::ip_mreq {
+            imr_multiaddr: libc::in_addr {
+                s_addr: u32::from_ne_bytes(multiaddr.octets()),
+            },
+            imr_interface: libc::in_addr {
+                s_addr: u32::from_ne_bytes(interface.octets()),
+            },
+        };
+
+        self.set_ip_mreq(libc::IP_ADD_MEMBERSHIP, mreq)
+    }
+
+    /// Executes an operation of the `IP_DROP_MEMBERSHIP` type.
+    ///
+    /// For more information about this option, see [`join_multicast_v4`].
+    ///
+    /// [`join_multicast_v4`]: UdpSocket::join_multicast_v4
+    pub fn leave_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        let mreq = libc::ip_mreq {
+            imr_multiaddr: libc::in_addr {
+                s_addr: u32::from_ne_bytes(multiaddr.octets()),
+            },
+            imr_interface: libc::in_addr {
+                s_addr: u32::from_ne_bytes(interface.octets()),
+            },
+        };
+
+        self.set_ip_mreq(libc::IP_DROP_MEMBERSHIP, mreq)
+    }
+
+    /// Executes an operation of the `IPV6_ADD_MEMBERSHIP` type.
+    ///
+    /// This function specifies a new multicast group for this socket to join.
+    /// The address must be a valid multicast address, and `interface` is the
+    /// index of the interface to join/leave (or `0` to indicate any
+    /// interface).
+    pub fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        let mreq = libc::ipv6_mreq {
+            ipv6mr_multiaddr: libc::in6_addr {
+                s6_addr: multiaddr.octets(),
+            },
+            ipv6mr_interface: interface,
+        };
+
+        self.set_ipv6_mreq(libc::IPV6_ADD_MEMBERSHIP, mreq)
+    }
+
+    /// Executes an operation of the `IPV6_DROP_MEMBERSHIP` type.
+    ///
+    /// For more information about this option, see [`join_multicast_v6`].
+    ///
+    /// [`join_multicast_v6`]: UdpSocket::join_multicast_v6
+    pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        let mreq = libc::ipv6_mreq {
+            ipv6mr_multiaddr: libc::in6_addr {
+                s6_addr: multiaddr.octets(),
+            },
+            ipv6mr_interface: interface,
+        };
+
+        self.set_ipv6_mreq(libc::IPV6_DROP_MEMBERSHIP, mreq)
+    }
+
+    /// Sets an `IPPROTO_IP`-level socket option taking an `ip_mreq` value.
+    fn set_ip_mreq(&self, opt: libc::c_int, mreq: libc::ip_mreq) -> io::Result<()> {
+        if unsafe {
+            libc::setsockopt(
+                self.io.as_raw_fd(),
+                libc::IPPROTO_IP,
+                opt,
+                (&raw const mreq).cast(),
+                mem::size_of::<libc::ip_mreq>() as libc::socklen_t,
+            )
+        } == -1
+        {
+            return Err(errno!("failed to set multicast membership"));
+        }
+
+        Ok(())
+    }
+
+    /// Sets an `IPPROTO_IPV6`-level socket option taking an `ipv6_mreq`
+    /// value.
+    fn set_ipv6_mreq(&self, opt: libc::c_int, mreq: libc::ipv6_mreq) -> io::Result<()> {
+        if unsafe {
+            libc::setsockopt(
+                self.io.as_raw_fd(),
+                libc::IPPROTO_IPV6,
+                opt,
+                (&raw const mreq).cast(),
+                mem::size_of::<libc::ipv6_mreq>() as libc::socklen_t,
+            )
+        } == -1
+        {
+            return Err(errno!("failed to set multicast membership"));
+        }
+
+        Ok(())
+    }
+
+    /// Sends data to the specified remote address.
+    pub async fn send_to(&self, buf: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        let (sock_addr_s, sock_len) = to_sockaddr_storage(addr);
+        future::poll_fn(|ctx| self.poll_send_to(ctx, buf, &sock_addr_s, sock_len)).await
+    }
+
+    /// Receives a datagram, returning the number of bytes read and the
+    /// address it was sent from.
+    ///
+    /// This issues a single `recvfrom` per call rather than draining the
+    /// socket of every queued datagram the way `TcpListener`/`UnixListener`
+    /// drain every pending connection out of `accept` before returning
+    /// `Pending`. That draining is needed there because an edge only fires
+    /// once no matter how many connections arrive before it's observed, so a
+    /// single `accept` per edge can miss a backlog. `recv_from` doesn't need
+    /// the same queue: it goes through `PollEvented::poll_read_with` (and so
+    /// `AsyncFd::try_io`), which always
+    /// attempts the syscall directly on every call instead of gating on a
+    /// cached-ready bit, so a second `recv_from().await` still picks up a
+    /// second buffered datagram with no fresh edge required, matching how
+    /// `recv`/`TcpStream::read` are single syscalls per call as well.
+    pub async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        future::poll_fn(|ctx| self.poll_recv_from(ctx, buf)).await
+    }
+
+    /// Sends data to the socket's connected peer.
+    pub async fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        future::poll_fn(|ctx| self.poll_send(ctx, buf)).await
+    }
+
+    /// Receives data from the socket's connected peer.
+    pub async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        future::poll_fn(|ctx| self.poll_recv(ctx, buf)).await
+    }
+
+    fn poll_send_to(
+        &self,
+        ctx: &mut Context<'_>,
+        buf: &[u8],
+        sock_addr_s: &libc::sockaddr_storage,
+        sock_len: libc::socklen_t,
+    ) -> Poll<io::Result<usize>> {
+        self.io.poll_write_with(ctx, |sock| {
+            let wbytes = unsafe {
+                libc::sendto(
+                    sock.as_raw_fd(),
+                    buf.as_ptr().cast(),
+                    buf.len(),
+                    0,
+                    &raw const *sock_addr_s as *const libc::sockaddr,
+                    sock_len,
+                )
+            };
+
+            if wbytes == -1 {
+                return Err(errno!("failed to send datagram"));
+            }
+
+            Ok(wbytes as usize)
+        })
+    }
+
+    fn poll_recv_from(
+        &self,
+        ctx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        self.io.poll_read_with(ctx, |sock| {
+            let mut sock_addr_s: libc::sockaddr_storage = unsafe { mem::zeroed() };
+            let mut sock_len = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+            let rbytes = unsafe {
+                libc::recvfrom(
+                    sock.as_raw_fd(),
+                    buf.as_mut_ptr().cast(),
+                    buf.len(),
+                    0,
+                    &raw mut sock_addr_s as *mut libc::sockaddr,
+                    &raw mut sock_len,
+                )
+            };
+
+            if rbytes == -1 {
+                return Err(errno!("failed to receive datagram"));
+            }
+
+            let addr = from_sockaddr_storage(&sock_addr_s)?;
+            Ok((rbytes as usize, addr))
+        })
+    }
+
+    fn poll_send(&self, ctx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.io.poll_write_with(ctx, |sock| {
+            let wbytes =
+                unsafe { libc::send(sock.as_raw_fd(), buf.as_ptr().cast(), buf.len(), 0) };
+
+            if wbytes == -1 {
+                return Err(errno!("failed to send datagram"));
+            }
+
+            Ok(wbytes as usize)
+        })
+    }
+
+    fn poll_recv(&self, ctx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.io.poll_read_with(ctx, |sock| {
+            let rbytes =
+                unsafe { libc::recv(sock.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len(), 0) };
+
+            if rbytes == -1 {
+                return Err(errno!("failed to receive datagram"));
+            }
+
+            Ok(rbytes as usize)
+        })
+    }
+}
+
+/// Resolves `addr` to its first candidate address.
+fn resolve_one<A: ToSocketAddrs>(addr: A) -> io::Result<SocketAddr> {
+    addr.to_socket_addrs()?.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "could not resolve any provided address",
+        )
+    })
+}
+
+impl AsRawFd for UdpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
+
+impl fmt::Debug for UdpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdpSocket")
+            .field("fd", &self.io.as_raw_fd())
+            .finish()
+    }
+}
+
+/// Thin `AsRawFd` wrapper around a raw, non-blocking `SOCK_DGRAM` file
+/// descriptor, so a bound UDP socket can be driven by [`PollEvented`] the same
+/// way `TcpStream` drives its `std::net::TcpStream`.
+struct RawUdpSocket(RawFd);
+
+impl AsRawFd for RawUdpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+impl Drop for RawUdpSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
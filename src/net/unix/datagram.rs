@@ -0,0 +1,89 @@
+use std::future;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+use std::task::{Context, Poll};
+
+use crate::rt::io::PollEvented;
+
+/// A Unix domain datagram socket.
+///
+/// After creating a `UnixDatagram` by `bind`ing it to a socket path, data can
+/// be sent to and received from any remote address with `send_to`/
+/// `recv_from`. A `UnixDatagram` may also be `connect`ed to a single remote
+/// address, after which `send`/`recv` can be used instead.
+#[derive(Debug)]
+pub struct UnixDatagram(PollEvented<std::os::unix::net::UnixDatagram>);
+
+impl UnixDatagram {
+    /// Creates a Unix datagram socket bound to the specified `path`.
+    pub async fn bind<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let sock = std::os::unix::net::UnixDatagram::bind(path)?;
+        Ok(UnixDatagram(PollEvented::new(sock)?))
+    }
+
+    /// Connects this socket to a remote address, restricting `send`/`recv` to
+    /// that peer.
+    pub fn connect<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.0.get_ref().connect(path)
+    }
+
+    /// Returns the local socket address that this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.get_ref().local_addr()
+    }
+
+    /// Sends data to the specified socket `path`.
+    pub async fn send_to<P: AsRef<Path>>(&self, buf: &[u8], path: P) -> io::Result<usize> {
+        future::poll_fn(|ctx| self.poll_send_to(ctx, buf, path.as_ref())).await
+    }
+
+    /// Receives a datagram, returning the number of bytes read and the
+    /// address it was sent from.
+    pub async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        future::poll_fn(|ctx| self.poll_recv_from(ctx, buf)).await
+    }
+
+    /// Sends data to the socket's connected peer.
+    pub async fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        future::poll_fn(|ctx| self.poll_send(ctx, buf)).await
+    }
+
+    /// Receives data from the socket's connected peer.
+    pub async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        future::poll_fn(|ctx| self.poll_recv(ctx, buf)).await
+    }
+
+    fn poll_send_to(
+        &self,
+        ctx: &mut Context<'_>,
+        buf: &[u8],
+        path: &Path,
+    ) -> Poll<io::Result<usize>> {
+        self.0
+            .poll_write_with(ctx, |sock| sock.send_to(buf, path))
+    }
+
+    fn poll_recv_from(
+        &self,
+        ctx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        self.0.poll_read_with(ctx, |sock| sock.recv_from(buf))
+    }
+
+    fn poll_send(&self, ctx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.poll_write_with(ctx, |sock| sock.send(buf))
+    }
+
+    fn poll_recv(&self, ctx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.0.poll_read_with(ctx, |sock| sock.recv(buf))
+    }
+}
+
+impl AsRawFd for UnixDatagram {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
@@ -0,0 +1,142 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::net::unix::UnixStream;
+use crate::rt::Runtime;
+
+/// A Unix domain socket server, listening for connections.
+#[derive(Debug)]
+pub struct UnixListener {
+    ln: std::os::unix::net::UnixListener,
+    /// In `EPOLLET` (edge-triggered mode), the listener must be fully drained,
+    /// as multiple connections may be ready to accept before `accept()` would
+    /// block again. To handle this, additional connections are queued.
+    queue: RefCell<VecDeque<(UnixStream, SocketAddr)>>,
+}
+
+impl UnixListener {
+    /// Creates a new `UnixListener` bound to the specified socket `path`.
+    ///
+    /// The returned listener is ready for accepting connections.
+    pub async fn bind<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let ln = std::os::unix::net::UnixListener::bind(path)?;
+
+        // Required to make sure `listener` can be polled without blocking when
+        // awaited.
+        ln.set_nonblocking(true)?;
+
+        Ok(UnixListener {
+            ln,
+            queue: RefCell::new(Default::default()),
+        })
+    }
+
+    /// Accepts a new incoming connection from this listener.
+    ///
+    /// This function will yield once a new connection is established. When
+    /// established, the corresponding `UnixStream` and the remote peer's
+    /// address will be returned.
+    pub async fn accept(&self) -> io::Result<(UnixStream, SocketAddr)> {
+        self.accept_one().await
+    }
+
+    /// Returns the local socket address that this listener is bound to.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.ln.local_addr()
+    }
+
+    /// Returns a `Future` that resolves to the next incoming connection.
+    #[inline]
+    fn accept_one(&self) -> AcceptFut<'_> {
+        AcceptFut(self)
+    }
+
+    /// Queues a connection given the `UnixStream` and remote address.
+    #[inline]
+    fn enqueue_connection(&self, stream: UnixStream, addr: SocketAddr) {
+        self.queue.borrow_mut().push_back((stream, addr));
+    }
+
+    /// Returns a queued accepted connection, or [`None`] if it is empty.
+    #[inline]
+    fn dequeue_connection(&self) -> Option<(UnixStream, SocketAddr)> {
+        self.queue.borrow_mut().pop_front()
+    }
+}
+
+impl AsRawFd for UnixListener {
+    fn as_raw_fd(&self) -> std::os::unix::prelude::RawFd {
+        self.ln.as_raw_fd()
+    }
+}
+
+impl Drop for UnixListener {
+    // SAFETY: The current runtime is guaranteed to be set via thread-local
+    // storage when entering `Runtime::block_on`, which is the only entry point
+    // for asynchronous execution, therefore, any async code, including this
+    // `Drop`, must be running within a valid runtime context to be called.
+    fn drop(&mut self) {
+        Runtime::current()
+            .scheduler
+            .unregister_fd(self.ln.as_raw_fd());
+
+        // Inner `std::os::unix::net::UnixListener` and queued connections are
+        // dropped...
+    }
+}
+
+/// A `Future` that resolves to the next incoming connection on a Unix
+/// listener.
+struct AcceptFut<'a>(&'a UnixListener);
+
+impl<'a> Future for AcceptFut<'a> {
+    type Output = io::Result<(UnixStream, SocketAddr)>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(conn_pair) = self.0.dequeue_connection() {
+            return Poll::Ready(Ok(conn_pair));
+        }
+
+        loop {
+            match self.0.ln.accept() {
+                Ok((stream, addr)) => match UnixStream::try_from(stream) {
+                    Ok(stream) => {
+                        self.0.enqueue_connection(stream, addr);
+                        continue;
+                    }
+                    Err(e) => return Poll::Ready(Err(e)),
+                },
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    // `EPOLLET` enables edge-triggered mode, notifying only
+                    // when changes occur on the monitored file descriptor,
+                    // rather than if it is in the desired state. This requires
+                    // non-blocking sockets and fully draining the socket of
+                    // reads/writes until it would block to avoid missing
+                    // events.
+                    let events = libc::EPOLLIN | libc::EPOLLET;
+                    Runtime::current().scheduler.register_fd(
+                        self.0.ln.as_raw_fd(),
+                        events as u32,
+                        ctx.waker().clone(),
+                    );
+
+                    // Connection may have been queued during draining loop.
+                    if let Some(conn_pair) = self.0.dequeue_connection() {
+                        return Poll::Ready(Ok(conn_pair));
+                    } else {
+                        return Poll::Pending;
+                    }
+                }
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+    }
+}
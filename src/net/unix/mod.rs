@@ -0,0 +1,8 @@
+mod listener;
+pub use listener::UnixListener;
+
+mod stream;
+pub use stream::UnixStream;
+
+mod datagram;
+pub use datagram::UnixDatagram;
@@ -0,0 +1,90 @@
+use std::io::{self, Read, Write};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use crate::io::AsyncRead;
+use crate::io::AsyncWrite;
+use crate::rt::io::PollEvented;
+
+/// A Unix domain socket stream between two local endpoints.
+///
+/// Reading and writing to a `UnixStream` is usually done using the methods
+/// found on the `AsyncRead` and `AsyncWrite` traits.
+#[derive(Debug)]
+pub struct UnixStream(PollEvented<std::os::unix::net::UnixStream>);
+
+impl UnixStream {
+    /// Connects to the Unix domain socket at the specified `path`.
+    pub async fn connect<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let stream = std::os::unix::net::UnixStream::connect(path)?;
+        UnixStream::try_from(stream)
+    }
+
+    /// Returns the socket address of the local half of this connection.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.get_ref().local_addr()
+    }
+
+    /// Returns the socket address of the remote half of this connection.
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.0.get_ref().peer_addr()
+    }
+}
+
+impl TryFrom<std::os::unix::net::UnixStream> for UnixStream {
+    type Error = io::Error;
+
+    fn try_from(stream: std::os::unix::net::UnixStream) -> Result<Self, Self::Error> {
+        // `PollEvented::new` sets the fd non-blocking so `stream` can be
+        // polled without blocking when awaited, and unregisters it from the
+        // I/O driver on drop.
+        Ok(UnixStream(PollEvented::new(stream)?))
+    }
+}
+
+impl AsRawFd for UnixStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl AsyncRead for UnixStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_read_with(ctx, |stream| {
+            let mut stream = stream;
+            stream.read(buf)
+        })
+    }
+}
+
+impl AsyncWrite for UnixStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_write_with(ctx, |stream| {
+            let mut stream = stream;
+            stream.write(buf)
+        })
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0
+            .poll_write_with(ctx, |stream| stream.shutdown(std::net::Shutdown::Write))
+    }
+}
@@ -0,0 +1,284 @@
+use std::future::Future;
+use std::io;
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use std::thread;
+
+use crate::rt::CURRENT_RUNTIME;
+use crate::rt::task::TaskId;
+
+/// Default number of worker threads the `spawn_blocking` pool spawns,
+/// absent an explicit `Builder::max_blocking_threads`.
+pub(crate) const DEFAULT_MAX_BLOCKING_THREADS: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Bounded pool of OS threads that runs blocking/CPU-bound closures off the
+/// single-threaded scheduler's own thread, backing `spawn_blocking`.
+///
+/// Everything crossing back from a worker thread to the scheduler goes
+/// through `Send` types only (the job itself, the completed-task list, and
+/// a self-pipe used to wake `epoll_wait`); the scheduler's `Task`/
+/// `TaskWaker` machinery, deliberately `!Send`, is never touched off-thread.
+#[derive(Debug)]
+pub(crate) struct BlockingPool {
+    sender: Sender<Job>,
+    /// IDs of tasks whose blocking job has completed, drained by the
+    /// scheduler (on its own thread) each tick.
+    completed: Arc<Mutex<Vec<TaskId>>>,
+    /// Read end of the self-pipe registered with the I/O driver, so
+    /// `epoll_wait` wakes promptly when a worker finishes a job while the
+    /// scheduler is blocked waiting on I/O.
+    notify_read: RawFd,
+    /// Write end of the same pipe, written to by worker threads.
+    notify_write: RawFd,
+}
+
+impl BlockingPool {
+    /// Spawns `size` worker threads (at least one), named using `name` as a
+    /// prefix if given.
+    pub(crate) fn new(size: usize, name: Option<&str>) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let (notify_read, notify_write) = new_pipe();
+
+        for idx in 0..size.max(1) {
+            let receiver = Arc::clone(&receiver);
+
+            let mut builder = thread::Builder::new();
+            if let Some(name) = name {
+                builder = builder.name(format!("{name}-{idx}"));
+            }
+
+            builder
+                .spawn(move || {
+                    loop {
+                        // The lock is only held long enough to pull the next
+                        // job off, never across running it, so workers don't
+                        // serialize on each other.
+                        let job = receiver
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .recv();
+
+                        match job {
+                            Ok(job) => job(),
+                            Err(_) => break,
+                        }
+                    }
+                })
+                .expect("failed to spawn `spawn_blocking` worker thread");
+        }
+
+        BlockingPool {
+            sender,
+            completed: Arc::new(Mutex::new(Vec::new())),
+            notify_read,
+            notify_write,
+        }
+    }
+
+    /// Runs `job` on the pool, recording `task_id` as completed (and waking
+    /// `epoll_wait` if the scheduler is currently blocked) once it returns.
+    pub(crate) fn submit(&self, task_id: TaskId, job: impl FnOnce() + Send + 'static) {
+        let completed = Arc::clone(&self.completed);
+        let notify_write = self.notify_write;
+
+        // A closed receiver means every worker thread has exited; there's
+        // nowhere left to run `job`, so just drop it rather than panicking.
+        let _ = self.sender.send(Box::new(move || {
+            job();
+
+            completed
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(task_id);
+
+            notify(notify_write);
+        }));
+    }
+
+    /// File descriptor to register with the I/O driver so `epoll_wait`
+    /// wakes up when a job completes.
+    pub(crate) fn notify_fd(&self) -> RawFd {
+        self.notify_read
+    }
+
+    /// Drains the self-pipe and returns the IDs of every task whose job has
+    /// completed since the last call.
+    pub(crate) fn drain_completed(&self) -> Vec<TaskId> {
+        drain_pipe(self.notify_read);
+
+        let mut completed = self.completed.lock().unwrap_or_else(PoisonError::into_inner);
+        mem::take(&mut *completed)
+    }
+}
+
+/// Creates a non-blocking pipe, returning `(read_fd, write_fd)`.
+///
+/// # Panics
+///
+/// Panics if the pipe could not be created.
+fn new_pipe() -> (RawFd, RawFd) {
+    let mut fds = [0; 2];
+
+    // SAFETY: `fds` is a valid, appropriately-sized buffer for `pipe2` to
+    // write the resulting file descriptors into.
+    let ret = unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) };
+
+    if ret != 0 {
+        panic!(
+            "failed to create `spawn_blocking` notify pipe: {}",
+            io::Error::last_os_error()
+        );
+    }
+
+    (fds[0], fds[1])
+}
+
+/// Writes a single byte to `fd`, waking up anyone blocked in `epoll_wait`
+/// on its read end. Best-effort: a full pipe buffer already means the read
+/// end has data pending, so a failed write changes nothing observable.
+fn notify(fd: RawFd) {
+    let byte = 1u8;
+
+    // SAFETY: `fd` is the write end of a pipe created by `new_pipe` and
+    // still open for the life of the owning `BlockingPool`.
+    unsafe {
+        libc::write(fd, &byte as *const u8 as *const libc::c_void, 1);
+    }
+}
+
+/// Reads from `fd` until it would block, discarding the bytes.
+///
+/// Required because the driver registers `fd` edge-triggered: a direction
+/// only re-fires once drained back to `WouldBlock`.
+fn drain_pipe(fd: RawFd) {
+    let mut buf = [0u8; 64];
+
+    loop {
+        // SAFETY: `fd` is the read end of a pipe created by `new_pipe`,
+        // valid for the life of the owning `BlockingPool`, and `buf` is a
+        // valid buffer of the given length.
+        let ret = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+
+        if ret <= 0 {
+            break;
+        }
+    }
+}
+
+/// Future wrapping a closure dispatched to the `spawn_blocking` pool.
+///
+/// Resolves once the closure finishes running on a worker thread. A panic
+/// in the closure is caught rather than propagated across the thread
+/// boundary; the caller decides what to do with it (see
+/// `Handle::spawn_blocking`).
+pub(crate) struct BlockingTask<F, R> {
+    job: Option<F>,
+    output: Arc<Mutex<Option<thread::Result<R>>>>,
+}
+
+impl<F, R> BlockingTask<F, R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    pub(crate) fn new(job: F) -> Self {
+        BlockingTask {
+            job: Some(job),
+            output: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl<F, R> Future for BlockingTask<F, R>
+where
+    F: FnOnce() -> R + Send + Unpin + 'static,
+    R: Send + 'static,
+{
+    type Output = thread::Result<R>;
+
+    fn poll(self: Pin<&mut Self>, _ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if let Some(result) = this
+            .output
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            return Poll::Ready(result);
+        }
+
+        if let Some(job) = this.job.take() {
+            let output = Arc::clone(&this.output);
+
+            CURRENT_RUNTIME.with(|rt| {
+                if let Some(ptr) = rt.get() {
+                    // SAFETY: The thread-local holds a raw pointer to a
+                    // `Runtime`. This pointer is only set via the entry
+                    // point `Runtime::block_on`, and cleared when the
+                    // associated `EnterGuard` is dropped. Polling a
+                    // `BlockingTask` is only possible within the context of
+                    // a runtime.
+                    let rt = unsafe { &*ptr };
+
+                    rt.scheduler.submit_blocking(move || {
+                        let result = panic::catch_unwind(AssertUnwindSafe(job));
+                        *output.lock().unwrap_or_else(PoisonError::into_inner) = Some(result);
+                    });
+                } else {
+                    panic!("`spawn_blocking` called outside of a runtime context");
+                }
+            });
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::rt::{JoinError, Runtime, spawn_blocking};
+
+    #[test]
+    fn pool_reports_completed_job() {
+        let pool = BlockingPool::new(1, None);
+        pool.submit(TaskId::from(7), || {});
+
+        for _ in 0..200 {
+            if pool.drain_completed() == [TaskId::from(7)] {
+                return;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        panic!("submitted job did not complete in time");
+    }
+
+    #[test]
+    fn spawn_blocking_resolves_with_closure_output() {
+        let rt = Runtime::new();
+        let result = rt.block_on(async { spawn_blocking(|| 2 + 2).await });
+
+        assert_eq!(result, Ok(4));
+    }
+
+    #[test]
+    fn spawn_blocking_reports_panics_as_join_error() {
+        let rt = Runtime::new();
+        let result: Result<(), JoinError> =
+            rt.block_on(async { spawn_blocking(|| panic!("boom")).await });
+
+        assert_eq!(result, Err(JoinError::Panicked));
+    }
+}
@@ -0,0 +1,104 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::rt::Runtime;
+use crate::rt::scheduler::{Scheduler, SchedulerConfig};
+
+/// Builder for configuring and constructing a [`Runtime`].
+///
+/// Created with [`Runtime::builder`].
+#[derive(Debug, Default)]
+pub struct Builder {
+    throttle: Option<Duration>,
+    event_interval: Option<u32>,
+    capacity_hint: Option<usize>,
+    thread_name: Option<Box<str>>,
+    max_blocking_threads: Option<usize>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` with rio's default configuration: the
+    /// scheduler re-enters `epoll_wait` immediately after every tick.
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Batches task polling under a throttled run loop.
+    ///
+    /// Instead of returning to `epoll_wait` as soon as the ready queue is
+    /// drained, the scheduler bounds how often it re-enters `epoll_wait` to
+    /// roughly once per `interval`, accumulating whichever tasks become ready
+    /// during that window into a single tick. This trades a small latency
+    /// increase for far fewer reactor cycles and better batching under high
+    /// event rates.
+    #[inline]
+    pub fn throttle(mut self, interval: Duration) -> Self {
+        self.throttle = Some(interval);
+        self
+    }
+
+    /// Bounds how many ready tasks a single scheduler tick polls before
+    /// returning to `epoll_wait`, instead of draining the entire ready queue.
+    ///
+    /// A large burst of runnable tasks (e.g. a long chain of tasks that keep
+    /// rescheduling each other) would otherwise delay the driver from
+    /// noticing newly ready file descriptors; bounding the tick keeps I/O
+    /// fairly interleaved with task polling. Defaults to 61.
+    #[inline]
+    pub fn event_interval(mut self, interval: u32) -> Self {
+        self.event_interval = Some(interval);
+        self
+    }
+
+    /// Pre-allocates the scheduler's task table and ready queue to hold at
+    /// least `capacity` tasks without reallocating.
+    #[inline]
+    pub fn max_tasks(mut self, capacity: usize) -> Self {
+        self.capacity_hint = Some(capacity);
+        self
+    }
+
+    /// Sets the name hint used for worker threads spawned for blocking work
+    /// (e.g. `spawn_blocking`'s pool). The runtime's own task polling always
+    /// runs on the thread that calls `block_on`.
+    #[inline]
+    pub fn thread_name(mut self, name: impl Into<String>) -> Self {
+        self.thread_name = Some(name.into().into_boxed_str());
+        self
+    }
+
+    /// Bounds how many worker threads the `spawn_blocking` pool spawns.
+    /// Defaults to 4.
+    ///
+    /// The pool is lazily created on the first `spawn_blocking` call, so
+    /// setting this has no effect on a runtime that never uses it.
+    #[inline]
+    pub fn max_blocking_threads(mut self, threads: usize) -> Self {
+        self.max_blocking_threads = Some(threads);
+        self
+    }
+
+    /// Builds the configured `Runtime`.
+    #[inline]
+    pub fn build(self) -> Runtime {
+        let mut config = SchedulerConfig {
+            throttle: self.throttle,
+            capacity_hint: self.capacity_hint,
+            thread_name: self.thread_name,
+            ..Default::default()
+        };
+
+        if let Some(interval) = self.event_interval {
+            config.event_interval = interval;
+        }
+
+        if let Some(threads) = self.max_blocking_threads {
+            config.max_blocking_threads = threads;
+        }
+
+        Runtime {
+            scheduler: Rc::new(Scheduler::with_config(config)),
+        }
+    }
+}
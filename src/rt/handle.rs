@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+
+use crate::rt::Runtime;
+use crate::rt::blocking::BlockingTask;
+use crate::rt::join::{JoinHandle, JoinState};
+use crate::rt::scheduler::Scheduler;
+
+/// A cheap, cloneable reference to a [`Runtime`]'s scheduler, letting code
+/// deep in a call tree spawn tasks or drive a future to completion without a
+/// `&Runtime` threaded through every function.
+///
+/// Like [`Runtime`], a `Handle` wraps an `Rc` and is therefore `!Send`/
+/// `!Sync`; it can only be used on the thread its runtime is entered on.
+#[derive(Debug, Clone)]
+pub struct Handle {
+    scheduler: Rc<Scheduler>,
+}
+
+impl Handle {
+    /// Returns a `Handle` to the runtime currently entered on this thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime context (i.e., outside of a
+    /// `Runtime::block_on` call).
+    pub fn current() -> Self {
+        Runtime::current().handle()
+    }
+
+    /// Creates a `Handle` sharing the given `scheduler`.
+    #[inline]
+    pub(crate) fn new(scheduler: Rc<Scheduler>) -> Self {
+        Handle { scheduler }
+    }
+
+    /// Spawns `future` onto this handle's runtime, returning a [`JoinHandle`]
+    /// that can be awaited to obtain its output.
+    ///
+    /// Equivalent to the free [`spawn`](crate::spawn) function, except it
+    /// doesn't rely on `CURRENT_RUNTIME` being set, as long as this `Handle`
+    /// was obtained beforehand.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+    {
+        let state = Rc::new(RefCell::new(JoinState::new()));
+        let join_state = Rc::clone(&state);
+
+        // Wrap `future` so its output, once resolved, is stored into the
+        // shared slot and any joiner waiting on the `JoinHandle` is woken.
+        let task_id = self.as_runtime().spawn_inner(async move {
+            let output = future.await;
+
+            let mut state = join_state.borrow_mut();
+            state.output = Some(output);
+
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        JoinHandle::new(task_id, Rc::clone(&self.scheduler), state)
+    }
+
+    /// Runs `f` on this handle's runtime's `spawn_blocking` worker pool,
+    /// returning a [`JoinHandle`] that resolves once it completes.
+    ///
+    /// Use this for blocking or CPU-bound work (e.g. synchronous file I/O, a
+    /// heavy computation) that would otherwise stall every other task on the
+    /// single-threaded scheduler. Unlike `spawn`, `f` must be `Send`, since
+    /// it runs on one of the pool's worker threads rather than the thread
+    /// driving the runtime. If `f` panics, the returned handle resolves to
+    /// `Err(JoinError::Panicked)` instead of propagating the panic onto the
+    /// worker thread.
+    ///
+    /// Equivalent to the free [`spawn_blocking`](crate::rt::spawn_blocking)
+    /// function, except it doesn't rely on `CURRENT_RUNTIME` being set, as
+    /// long as this `Handle` was obtained beforehand.
+    pub fn spawn_blocking<F, R>(&self, f: F) -> JoinHandle<R>
+    where
+        F: FnOnce() -> R + Send + Unpin + 'static,
+        R: Send + 'static,
+    {
+        let state = Rc::new(RefCell::new(JoinState::new()));
+        let join_state = Rc::clone(&state);
+
+        let task_id = self.as_runtime().spawn_inner(async move {
+            let result = BlockingTask::new(f).await;
+
+            let mut state = join_state.borrow_mut();
+            match result {
+                Ok(output) => state.output = Some(output),
+                Err(_) => state.panicked = true,
+            }
+
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        JoinHandle::new(task_id, Rc::clone(&self.scheduler), state)
+    }
+
+    /// Runs `future` to completion on this handle's runtime, blocking the
+    /// current thread until it resolves.
+    ///
+    /// See [`Runtime::block_on`].
+    pub fn block_on<F: Future + 'static>(&self, future: F) -> F::Output {
+        self.as_runtime().block_on(future)
+    }
+
+    /// Views this handle's shared scheduler as a standalone `Runtime`, to
+    /// reuse `Runtime`'s spawning and blocking logic instead of duplicating
+    /// it here.
+    #[inline]
+    fn as_runtime(&self) -> Runtime {
+        Runtime {
+            scheduler: Rc::clone(&self.scheduler),
+        }
+    }
+}
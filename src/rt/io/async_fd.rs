@@ -0,0 +1,274 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use crate::rt::Runtime;
+use crate::rt::io::errno;
+
+/// Direction of readiness an `AsyncFd` operation is interested in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interest {
+    /// `EPOLLIN`-class readiness (`EPOLLIN`, `EPOLLHUP`, `EPOLLERR`).
+    Read,
+    /// `EPOLLOUT` readiness.
+    Write,
+}
+
+impl Interest {
+    /// Bitmask of `epoll_event.events` values this interest corresponds to.
+    pub(crate) fn events(self) -> u32 {
+        match self {
+            Interest::Read => (libc::EPOLLIN | libc::EPOLLHUP | libc::EPOLLERR) as u32,
+            Interest::Write => libc::EPOLLOUT as u32,
+        }
+    }
+}
+
+/// Registers an arbitrary pollable file descriptor with the runtime's epoll
+/// `Driver`, turning the crate from "`TcpStream`-only" into a general reactor
+/// any `AsRawFd` type (pipes, `eventfd(2)`, `timerfd(2)`, UDP sockets, ...)
+/// can be built on top of, mirroring tokio's `AsyncFd` and smol's `Async<T>`.
+///
+/// Readiness for each direction is cached by the `Driver` itself (the fd is
+/// registered edge-triggered), so [`poll_read_ready`]/[`poll_write_ready`]
+/// first consult that cache before registering a waker; [`try_io`] clears the
+/// cached bit on `WouldBlock` so the task is only woken again on the next
+/// edge, per the `EPOLLET` invariant documented on [`Driver::clear_ready`].
+///
+/// [`poll_read_ready`]: AsyncFd::poll_read_ready
+/// [`poll_write_ready`]: AsyncFd::poll_write_ready
+/// [`try_io`]: AsyncFd::try_io
+/// [`Driver::clear_ready`]: crate::rt::io::Driver::clear_ready
+#[derive(Debug)]
+pub struct AsyncFd<T: AsRawFd> {
+    inner: T,
+}
+
+impl<T: AsRawFd> AsyncFd<T> {
+    /// Sets `inner`'s file descriptor non-blocking and prepares it to be
+    /// registered with the current runtime's I/O driver.
+    ///
+    /// Registration with the driver itself is deferred until the first
+    /// `poll_read_ready`/`poll_write_ready`/`try_io` call returns
+    /// `WouldBlock`, rather than happening eagerly here, so constructing an
+    /// `AsyncFd` doesn't require a runtime to be entered on the calling
+    /// thread. Only the operations above do, and they document that
+    /// themselves via `Runtime::current`.
+    pub fn new(inner: T) -> io::Result<Self> {
+        set_nonblocking(inner.as_raw_fd())?;
+
+        Ok(AsyncFd { inner })
+    }
+
+    /// Returns a shared reference to the wrapped value.
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a future that resolves to a [`ReadyGuard`] once the fd is
+    /// ready for reading.
+    #[inline]
+    pub fn readable(&self) -> Readiness<'_, T> {
+        Readiness {
+            io: self,
+            interest: Interest::Read,
+            waker: RefCell::new(None),
+        }
+    }
+
+    /// Returns a future that resolves to a [`ReadyGuard`] once the fd is
+    /// ready for writing.
+    #[inline]
+    pub fn writable(&self) -> Readiness<'_, T> {
+        Readiness {
+            io: self,
+            interest: Interest::Write,
+            waker: RefCell::new(None),
+        }
+    }
+
+    /// Polls the fd for read readiness, registering the current task to be
+    /// woken on the next `EPOLLIN`-class edge if not yet ready.
+    #[allow(unused)]
+    pub(crate) fn poll_read_ready(&self, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_ready(Interest::Read, ctx)
+    }
+
+    /// Polls the fd for write readiness, registering the current task to be
+    /// woken on the next `EPOLLOUT` edge if not yet ready.
+    #[allow(unused)]
+    pub(crate) fn poll_write_ready(&self, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_ready(Interest::Write, ctx)
+    }
+
+    /// Runs `op`, clearing the cached readiness for `interest` and
+    /// registering the current task with the driver if `op` returns
+    /// `WouldBlock`.
+    pub(crate) fn try_io<R>(
+        &self,
+        interest: Interest,
+        ctx: &mut Context<'_>,
+        mut op: impl FnMut() -> io::Result<R>,
+    ) -> Poll<io::Result<R>> {
+        match op() {
+            Ok(value) => Poll::Ready(Ok(value)),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.clear_ready(interest);
+                self.register(interest, ctx);
+                Poll::Pending
+            }
+            Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+
+    fn poll_ready(&self, interest: Interest, ctx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let scheduler = &Runtime::current().scheduler;
+
+        if scheduler.is_fd_ready(self.inner.as_raw_fd(), interest.events()) {
+            return Poll::Ready(Ok(()));
+        }
+
+        self.register(interest, ctx);
+        Poll::Pending
+    }
+
+    /// Clears the driver's cached readiness for `interest` on this fd.
+    ///
+    /// Must only be called once the corresponding read/write has actually
+    /// returned `WouldBlock`, since the driver's registrations are
+    /// edge-triggered and won't observe another edge until then.
+    #[inline]
+    fn clear_ready(&self, interest: Interest) {
+        Runtime::current()
+            .scheduler
+            .clear_fd_ready(self.inner.as_raw_fd(), interest.events());
+    }
+
+    /// Registers `interest` with the scheduler's I/O driver.
+    fn register(&self, interest: Interest, ctx: &mut Context<'_>) {
+        Runtime::current().scheduler.register_fd(
+            self.inner.as_raw_fd(),
+            interest.events(),
+            ctx.waker().clone(),
+        );
+    }
+}
+
+impl<T: AsRawFd> AsRawFd for AsyncFd<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl<T: AsRawFd> Drop for AsyncFd<T> {
+    // SAFETY: The current runtime is guaranteed to be set via thread-local
+    // storage when entering `Runtime::block_on`, which is the only entry
+    // point for asynchronous execution, therefore, any async code, including
+    // this `Drop`, must be running within a valid runtime context to be
+    // called.
+    fn drop(&mut self) {
+        Runtime::current()
+            .scheduler
+            .unregister_fd(self.inner.as_raw_fd());
+    }
+}
+
+/// Future returned by [`AsyncFd::readable`]/[`AsyncFd::writable`], resolving
+/// to a [`ReadyGuard`] once the fd reports readiness for `interest`.
+#[derive(Debug)]
+pub struct Readiness<'a, T: AsRawFd> {
+    io: &'a AsyncFd<T>,
+    interest: Interest,
+    /// Waker last registered with the driver while pending, so `Drop` can
+    /// deregister it if this future is cancelled before resolving.
+    waker: RefCell<Option<Waker>>,
+}
+
+impl<'a, T: AsRawFd> Future for Readiness<'a, T> {
+    type Output = io::Result<ReadyGuard<'a, T>>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        match this.io.poll_ready(this.interest, ctx) {
+            Poll::Ready(Ok(())) => {
+                this.waker.borrow_mut().take();
+
+                Poll::Ready(Ok(ReadyGuard {
+                    io: this.io,
+                    interest: this.interest,
+                }))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => {
+                *this.waker.borrow_mut() = Some(ctx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T: AsRawFd> Drop for Readiness<'_, T> {
+    // SAFETY: The current runtime is guaranteed to be set via thread-local
+    // storage when entering `Runtime::block_on`, which is the only entry
+    // point for asynchronous execution, therefore, any async code, including
+    // this `Drop`, must be running within a valid runtime context to be
+    // called.
+    fn drop(&mut self) {
+        // Dropped after resolving (or without ever being polled pending):
+        // nothing was left registered.
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            Runtime::current().scheduler.deregister_waker(
+                self.io.inner.as_raw_fd(),
+                self.interest.events(),
+                &waker,
+            );
+        }
+    }
+}
+
+/// Guard returned once an [`AsyncFd`] reports readiness for a direction, by
+/// [`AsyncFd::readable`]/[`AsyncFd::writable`].
+///
+/// If the I/O attempt made while holding this guard returns `WouldBlock`
+/// anyway (a false wake), call [`clear_ready`] before awaiting
+/// `readable()`/`writable()` again, so the next wait is for a new edge rather
+/// than the driver re-reporting the same stale readiness.
+///
+/// [`clear_ready`]: ReadyGuard::clear_ready
+#[derive(Debug)]
+pub struct ReadyGuard<'a, T: AsRawFd> {
+    io: &'a AsyncFd<T>,
+    interest: Interest,
+}
+
+impl<T: AsRawFd> ReadyGuard<'_, T> {
+    /// Clears the driver's cached readiness for this guard's direction.
+    ///
+    /// Must only be called once the corresponding read/write has actually
+    /// returned `WouldBlock`, since the fd is registered edge-triggered and
+    /// won't observe another edge until then.
+    pub fn clear_ready(&self) {
+        self.io.clear_ready(self.interest);
+    }
+}
+
+/// Sets the `O_NONBLOCK` flag on `fd`.
+fn set_nonblocking(fd: RawFd) -> io::Result<()> {
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL);
+        if flags == -1 {
+            return Err(errno!("failed to read fd flags"));
+        }
+
+        if libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) == -1 {
+            return Err(errno!("failed to set fd non-blocking"));
+        }
+    }
+
+    Ok(())
+}
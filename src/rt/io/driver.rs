@@ -1,10 +1,75 @@
 use std::collections::HashMap;
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::task::Waker;
-use std::{io, ptr};
+use std::{io, mem, ptr};
 
 use crate::rt::io::errno;
 
+/// Per-direction readiness tracked for a single registered file descriptor.
+///
+/// `epoll(7)` raises interest per fd rather than per direction, so a socket
+/// with one task waiting to read and another waiting to write needs separate
+/// slots for each — otherwise the second registration clobbers the first.
+#[derive(Debug, Default)]
+struct ScheduledIo {
+    /// Bitmask of interest currently armed with `epoll(7)` for this fd, the
+    /// union of every direction registered since the fd was added. It is not
+    /// narrowed when a direction's wakers are drained or removed.
+    interest: u32,
+    /// Bitmask of directions (`Driver::READABLE`/`Driver::WRITABLE`) cached as
+    /// ready since the last edge, independent of whether any wakers are
+    /// currently registered.
+    ///
+    /// Because registration uses `EPOLLET`, an edge is only reported once:
+    /// callers MUST drain their read/write to `WouldBlock` and clear the bit
+    /// via [`Driver::clear_ready`] before the next edge can be observed,
+    /// otherwise readiness is lost for good.
+    ready: u32,
+    /// `Waker`s for every task awaiting `EPOLLIN`-class readiness.
+    ///
+    /// More than one task may legitimately wait on the same direction of the
+    /// same fd (e.g. a shared socket), so this is a list rather than a single
+    /// slot; all of them are woken on the next matching edge.
+    read_wakers: Vec<Waker>,
+    /// `Waker`s for every task awaiting `EPOLLOUT` readiness. See
+    /// `read_wakers` for why this is a list.
+    write_wakers: Vec<Waker>,
+}
+
+impl ScheduledIo {
+    /// Registers `waker` as waiting on `direction`, replacing any existing
+    /// entry for a task `waker` already wakes (so repeatedly polling the same
+    /// task doesn't grow the list without bound).
+    fn push_waker(&mut self, direction: u32, waker: Waker) {
+        let list = self.wakers_mut(direction);
+        list.retain(|w| !w.will_wake(&waker));
+        list.push(waker);
+    }
+
+    /// Removes any registered `Waker` that wakes the same task as `waker`
+    /// from `direction`'s list.
+    ///
+    /// Called when a future waiting on this fd/direction is dropped before
+    /// being woken, so its entry doesn't sit in the list until some unrelated
+    /// edge happens to flush it out.
+    fn remove_waker(&mut self, direction: u32, waker: &Waker) {
+        self.wakers_mut(direction).retain(|w| !w.will_wake(waker));
+    }
+
+    /// Drains and returns every `Waker` registered for `direction`.
+    fn take_wakers(&mut self, direction: u32) -> Vec<Waker> {
+        mem::take(self.wakers_mut(direction))
+    }
+
+    fn wakers_mut(&mut self, direction: u32) -> &mut Vec<Waker> {
+        if direction == Driver::WRITABLE {
+            &mut self.write_wakers
+        } else {
+            &mut self.read_wakers
+        }
+    }
+}
+
 /// I/O driver backed by `epoll(7)`.
 ///
 /// Handles the registering and waiting on I/O events, waking tasks when
@@ -15,14 +80,30 @@ pub(crate) struct Driver {
     epoll_fd: RawFd,
     /// Stores events for ready file descriptors.
     events: [libc::epoll_event; Self::EPOLL_MAX_EVENTS as usize],
-    /// Associates file descriptors with their corresponding [`Waker`].
-    registered: HashMap<RawFd, Waker>,
+    /// Associates file descriptors with their per-direction readiness state.
+    registered: HashMap<RawFd, ScheduledIo>,
 }
 
 impl Driver {
     /// Total number of events returned each tick (event loop cycle).
     const EPOLL_MAX_EVENTS: i32 = 1024;
 
+    /// Bitmask of `epoll_event.events` values that indicate read-direction
+    /// readiness: the fd is readable, or hung up/errored (both of which must
+    /// also wake a pending reader so it can observe the EOF/error).
+    const READABLE: u32 = (libc::EPOLLIN | libc::EPOLLHUP | libc::EPOLLERR) as u32;
+
+    /// Bitmask of `epoll_event.events` values that indicate write-direction
+    /// readiness.
+    const WRITABLE: u32 = libc::EPOLLOUT as u32;
+
+    /// `EPOLLET`, OR'd into every registration so the driver only ever
+    /// receives one notification per edge instead of firing repeatedly while
+    /// a socket remains readable/writable. Paired with the cached `ready`
+    /// bits on `ScheduledIo` so a readiness check doesn't need to touch
+    /// `epoll_wait` at all once the bit is set.
+    const EDGE_TRIGGERED: u32 = libc::EPOLLET as u32;
+
     /// Creates a new `Reactor` instance.
     ///
     /// # Panics
@@ -45,6 +126,13 @@ impl Driver {
     /// timeout of `0` will not wait on any file descriptors to be ready before
     /// returning.
     ///
+    /// Registrations use `EPOLLET` (edge-triggered), so each event is only
+    /// reported once per edge. For every ready direction, the corresponding
+    /// bit is OR'd into the fd's cached `ready` mask and every waker waiting
+    /// on that direction is drained and woken; the fd's epoll interest is
+    /// left untouched, since edge-triggered mode will not re-fire spuriously
+    /// for a direction that's already ready.
+    ///
     /// # Panics
     ///
     /// This function panics if it fails to wait on file descriptor readiness.
@@ -71,43 +159,125 @@ impl Driver {
                 let fd = event.u64 as i32;
                 let events = event.events;
 
-                if let Some(waker) = self.registered.get(&fd) {
-                    waker.wake_by_ref();
+                let Some(io) = self.registered.get_mut(&fd) else {
+                    continue;
+                };
+
+                if events & Self::READABLE != 0 {
+                    io.ready |= Self::READABLE;
+                    for waker in io.take_wakers(Self::READABLE) {
+                        waker.wake();
+                    }
+                }
+
+                if events & Self::WRITABLE != 0 {
+                    io.ready |= Self::WRITABLE;
+                    for waker in io.take_wakers(Self::WRITABLE) {
+                        waker.wake();
+                    }
                 }
             }
         }
     }
 
-    /// Add an entry to the interest list of the `epoll(7)` file descriptor.
-    /// Each event is associated to a given [`Waker`].
+    /// Returns `true` if `fd` has a cached readiness bit set for every
+    /// direction in `interest` (a combination of `Self::READABLE`/
+    /// `Self::WRITABLE`), without making an `epoll_wait` call.
     ///
-    /// `events` is a bit mask of event types (`epoll_ctl(2)`).
+    /// A freshly registered fd has no cached readiness until the first edge
+    /// is observed by [`poll`], so callers should attempt the syscall on
+    /// first registration rather than waiting on this to become `true`.
     ///
-    /// If the given file descriptor already exists within the interest list,
-    /// the settings associated with it will be updated to `events`.
+    /// [`poll`]: Driver::poll
+    pub(crate) fn is_ready(&self, fd: RawFd, interest: u32) -> bool {
+        self.registered
+            .get(&fd)
+            .is_some_and(|io| io.ready & interest == interest)
+    }
+
+    /// Clears the cached readiness bit(s) in `interest` for `fd`.
+    ///
+    /// Because registrations use `EPOLLET`, an edge for a direction is only
+    /// reported once: callers MUST drain the fd for that direction to
+    /// `WouldBlock` before clearing its bit here, otherwise the pending
+    /// readiness is lost until some unrelated event happens to re-arm it.
+    pub(crate) fn clear_ready(&mut self, fd: RawFd, interest: u32) {
+        if let Some(io) = self.registered.get_mut(&fd) {
+            io.ready &= !interest;
+        }
+    }
+
+    /// Removes `waker`'s entry (or any entry waking the same task) from the
+    /// direction(s) in `interest` for `fd`.
+    ///
+    /// Intended to be called when a future waiting on an fd/direction is
+    /// dropped before being woken, so a cancelled wait doesn't leave a stale
+    /// `Waker` sitting in the list until an unrelated edge happens to drain
+    /// it. A no-op if `fd` isn't registered.
+    pub(crate) fn deregister_waker(&mut self, fd: RawFd, interest: u32, waker: &Waker) {
+        let Some(io) = self.registered.get_mut(&fd) else {
+            return;
+        };
+
+        if interest & Self::READABLE != 0 {
+            io.remove_waker(Self::READABLE, waker);
+        }
+        if interest & Self::WRITABLE != 0 {
+            io.remove_waker(Self::WRITABLE, waker);
+        }
+    }
+
+    /// Registers interest in `events` for the given file descriptor, adding
+    /// `waker` to the direction(s) it covers (`EPOLLIN`-class and/or
+    /// `EPOLLOUT`).
+    ///
+    /// Multiple tasks may register on the same fd/direction at once (e.g. a
+    /// shared socket); all of them are woken on the next matching edge. If
+    /// `waker` wakes the same task as one already registered for a direction,
+    /// the older entry is replaced rather than duplicated.
+    ///
+    /// If the file descriptor is already registered, its outstanding interest
+    /// is OR'd with `events` so a single `epoll_ctl` reflects both directions.
     ///
     /// # Panics
     ///
     /// This function panics if the entry could not be added to the interest
     /// list.
     pub(crate) fn register(&mut self, fd: RawFd, events: u32, waker: Waker) {
-        let mut ev = libc::epoll_event {
-            events,
-            u64: fd as u64,
-        };
+        let is_new = !self.registered.contains_key(&fd);
+        let io = self.registered.entry(fd).or_default();
 
-        if unsafe { libc::epoll_ctl(self.epoll_fd, libc::EPOLL_CTL_ADD, fd, &raw mut ev) } == -1 {
-            // The supplied file descriptor is already registered with this
-            // `epoll` instance.
-            if io::Error::last_os_error().raw_os_error() == Some(libc::EEXIST) {
-                self.modify(fd, events);
-                return;
-            }
-
-            panic!("{}", errno!("failed to add to epoll interest list"));
+        if events & Self::READABLE != 0 {
+            io.push_waker(Self::READABLE, waker.clone());
+        }
+        if events & Self::WRITABLE != 0 {
+            io.push_waker(Self::WRITABLE, waker);
         }
 
-        self.registered.insert(fd, waker);
+        io.interest |= events;
+        let interest = io.interest | Self::EDGE_TRIGGERED;
+
+        if is_new {
+            let mut ev = libc::epoll_event {
+                events: interest,
+                u64: fd as u64,
+            };
+
+            if unsafe { libc::epoll_ctl(self.epoll_fd, libc::EPOLL_CTL_ADD, fd, &raw mut ev) }
+                == -1
+            {
+                // The supplied file descriptor is already registered with
+                // this `epoll` instance.
+                if io::Error::last_os_error().raw_os_error() == Some(libc::EEXIST) {
+                    self.modify(fd, interest);
+                    return;
+                }
+
+                panic!("{}", errno!("failed to add to epoll interest list"));
+            }
+        } else {
+            self.modify(fd, interest);
+        }
     }
 
     /// Change the settings associated with the file descriptor in `epoll(7)`
@@ -140,15 +310,14 @@ impl Driver {
     }
 
     /// Remove (unregister) the target file descriptor from the `epoll(7)`
-    /// interest list, returning the associated `Waker`, or `None` if the entry
-    /// did not exist.
+    /// interest list, dropping any wakers still associated with it.
     ///
     /// # Panics
     ///
     /// This function panics if the file descriptor could not be unregistered.
-    pub(crate) fn unregister(&mut self, fd: RawFd) -> Option<Waker> {
+    pub(crate) fn unregister(&mut self, fd: RawFd) {
         self.unregister_fd(fd);
-        self.registered.remove(&fd)
+        self.registered.remove(&fd);
     }
 
     /// Remove (unregister) the target file descriptor from the `epoll(7)`
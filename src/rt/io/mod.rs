@@ -6,6 +6,12 @@
 mod driver;
 pub(crate) use driver::Driver;
 
+mod async_fd;
+pub use async_fd::{AsyncFd, Interest, ReadyGuard, Readiness};
+
+mod poll_evented;
+pub(crate) use poll_evented::PollEvented;
+
 /// Creates an [Error::Io] with a message prefixed to the `errno` value.
 macro_rules! errno {
     ($($arg:tt)+) => {{
@@ -0,0 +1,77 @@
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::task::{Context, Poll};
+
+use crate::rt::io::async_fd::{AsyncFd, Interest};
+
+/// Drives a closure-based read/write retry loop over an arbitrary file
+/// descriptor, registering with the runtime's epoll `Driver` the first time a
+/// read/write would block and unregistering on drop.
+///
+/// Built on top of [`AsyncFd`]: where `AsyncFd` exposes raw readiness
+/// (`poll_read_ready`/`try_io`), `PollEvented` is the ergonomic "run this
+/// syscall, retry once the driver says we're ready" layer socket types are
+/// expected to hold, mirroring Tokio's `PollEvented`. This removes the need
+/// for every socket type to hand-roll the same `match .. WouldBlock =>
+/// register_fd(..)` dance in each of its `poll_read`/`poll_write` methods.
+#[derive(Debug)]
+pub(crate) struct PollEvented<T: AsRawFd> {
+    io: AsyncFd<T>,
+}
+
+impl<T: AsRawFd> PollEvented<T> {
+    /// Sets `io`'s file descriptor non-blocking and prepares it to be
+    /// registered with the current runtime's I/O driver.
+    ///
+    /// Registration itself is deferred to the first `poll_read_with`/
+    /// `poll_write_with` call that returns `WouldBlock`; see
+    /// [`AsyncFd::new`].
+    pub(crate) fn new(io: T) -> io::Result<Self> {
+        Ok(PollEvented {
+            io: AsyncFd::new(io)?,
+        })
+    }
+
+    /// Returns a shared reference to the wrapped value.
+    #[inline]
+    pub(crate) fn get_ref(&self) -> &T {
+        self.io.get_ref()
+    }
+
+    /// Runs `op` against the wrapped value, registering the current task to
+    /// be woken on the next `EPOLLIN`-class edge and returning `Poll::Pending`
+    /// if it returns `WouldBlock`.
+    pub(crate) fn poll_read_with<R>(
+        &self,
+        ctx: &mut Context<'_>,
+        op: impl FnMut(&T) -> io::Result<R>,
+    ) -> Poll<io::Result<R>> {
+        self.poll_with(Interest::Read, ctx, op)
+    }
+
+    /// Runs `op` against the wrapped value, registering the current task to
+    /// be woken on the next `EPOLLOUT` edge and returning `Poll::Pending` if
+    /// it returns `WouldBlock`.
+    pub(crate) fn poll_write_with<R>(
+        &self,
+        ctx: &mut Context<'_>,
+        op: impl FnMut(&T) -> io::Result<R>,
+    ) -> Poll<io::Result<R>> {
+        self.poll_with(Interest::Write, ctx, op)
+    }
+
+    fn poll_with<R>(
+        &self,
+        interest: Interest,
+        ctx: &mut Context<'_>,
+        mut op: impl FnMut(&T) -> io::Result<R>,
+    ) -> Poll<io::Result<R>> {
+        self.io.try_io(interest, ctx, || op(self.io.get_ref()))
+    }
+}
+
+impl<T: AsRawFd> AsRawFd for PollEvented<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.io.as_raw_fd()
+    }
+}
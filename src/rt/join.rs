@@ -0,0 +1,215 @@
+use std::cell::RefCell;
+use std::error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use crate::rt::task::{SchedulerRef, TaskId};
+
+/// Error returned by a [`JoinHandle`] when its task did not run to
+/// completion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinError {
+    /// The task was cancelled via [`JoinHandle::abort`] before it completed.
+    Cancelled,
+    /// The task panicked while running, e.g. a `spawn_blocking` closure that
+    /// unwound on a worker thread.
+    Panicked,
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinError::Cancelled => write!(f, "task was cancelled"),
+            JoinError::Panicked => write!(f, "task panicked"),
+        }
+    }
+}
+
+impl error::Error for JoinError {}
+
+/// Shared slot a spawned task's harness writes its output into, and the
+/// associated [`JoinHandle`] polls to retrieve it.
+///
+/// Held by both the task's future (via the harness `spawn` wraps it in) and
+/// the `JoinHandle`, so the output survives the task being dropped from the
+/// `Scheduler` once it resolves.
+pub(crate) struct JoinState<T> {
+    /// The task's output, once it has resolved.
+    pub(crate) output: Option<T>,
+    /// Waker for the task currently awaiting this handle, registered the last
+    /// time the slot was polled while empty.
+    pub(crate) waker: Option<Waker>,
+    /// Set by [`JoinHandle::abort`]; once `true`, the handle resolves to
+    /// [`JoinError::Cancelled`] instead of waiting on `output`, since the
+    /// task's future has already been dropped out of the scheduler and will
+    /// never fill it in.
+    pub(crate) cancelled: bool,
+    /// Set by a task's completion harness when the task panicked instead of
+    /// producing an output (e.g. a `spawn_blocking` closure that unwound);
+    /// once `true`, the handle resolves to [`JoinError::Panicked`].
+    pub(crate) panicked: bool,
+}
+
+impl<T> JoinState<T> {
+    /// Creates a new, empty `JoinState`.
+    #[inline]
+    pub(crate) fn new() -> Self {
+        JoinState {
+            output: None,
+            waker: None,
+            cancelled: false,
+            panicked: false,
+        }
+    }
+}
+
+/// An owned handle to a spawned task, allowing its output to be awaited.
+///
+/// Dropping a `JoinHandle` does not cancel the associated task; it continues
+/// running to completion in the background. Use [`abort`] to cancel it
+/// explicitly.
+///
+/// [`abort`]: JoinHandle::abort
+pub struct JoinHandle<T> {
+    task_id: TaskId,
+    scheduler: SchedulerRef,
+    state: Rc<RefCell<JoinState<T>>>,
+}
+
+impl<T> JoinHandle<T> {
+    /// Creates a new `JoinHandle` sharing the given result slot with the
+    /// task's completion harness.
+    #[inline]
+    pub(crate) fn new(
+        task_id: TaskId,
+        scheduler: impl Into<SchedulerRef>,
+        state: Rc<RefCell<JoinState<T>>>,
+    ) -> Self {
+        JoinHandle {
+            task_id,
+            scheduler: scheduler.into(),
+            state,
+        }
+    }
+
+    /// Cancels the task, if it hasn't already completed.
+    ///
+    /// The task's future is dropped out of the scheduler without being polled
+    /// again, and subsequent `poll`s of this handle resolve to
+    /// `Err(JoinError::Cancelled)`. A task that has already produced its
+    /// output is unaffected, since `abort` cannot retract a result that's
+    /// already been written to the shared slot.
+    ///
+    /// Unlike `Runtime::current`-based lookups, this calls `cancel_task` on
+    /// the scheduler the task was actually spawned on, so it works
+    /// regardless of which (if any) runtime is entered on the calling
+    /// thread.
+    pub fn abort(&self) {
+        let waker = {
+            let mut state = self.state.borrow_mut();
+
+            if state.output.is_some() || state.panicked {
+                return;
+            }
+
+            state.cancelled = true;
+            state.waker.take()
+        };
+
+        self.scheduler.cancel_task(self.task_id);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+
+        if let Some(output) = state.output.take() {
+            return Poll::Ready(Ok(output));
+        }
+
+        if state.cancelled {
+            return Poll::Ready(Err(JoinError::Cancelled));
+        }
+
+        if state.panicked {
+            return Poll::Ready(Err(JoinError::Panicked));
+        }
+
+        // Register interest so the completion harness can wake us once the
+        // task resolves.
+        state.waker = Some(ctx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinHandle")
+            .field("task_id", &self.task_id)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::pending;
+
+    use crate::rt::Runtime;
+
+    use super::*;
+
+    #[test]
+    fn abort_cancels_pending_task() {
+        let rt = Runtime::new();
+
+        rt.block_on(async {
+            let handle = crate::rt::spawn(pending::<()>());
+            handle.abort();
+
+            assert_eq!(handle.await, Err(JoinError::Cancelled));
+        });
+    }
+
+    #[test]
+    fn abort_after_completion_is_noop() {
+        // Once a `JoinState`'s output slot is already filled, `abort` must
+        // leave it alone rather than retroactively cancelling a result the
+        // task already produced.
+        let rt = Runtime::new();
+        let state = Rc::new(RefCell::new(JoinState::new()));
+        state.borrow_mut().output = Some(42);
+
+        let handle = JoinHandle::new(TaskId::from(0), Rc::clone(&rt.scheduler), state);
+        handle.abort();
+
+        assert!(!handle.state.borrow().cancelled);
+    }
+
+    #[test]
+    fn abort_without_entered_runtime_cancels_task() {
+        // Regression test: `JoinHandle::abort` must cancel the task on the
+        // scheduler it was actually spawned with, not whatever happens to be
+        // thread-locally "current". Calling it here, with no `block_on` in
+        // progress on this thread at all, must not panic.
+        let rt = Runtime::new();
+        let handle = rt.handle();
+
+        let join = handle.spawn(pending::<()>());
+        join.abort();
+
+        // The task is gone from the scheduler, so there's nothing left to
+        // cancel.
+        assert!(!rt.scheduler.cancel_task(join.task_id));
+    }
+}
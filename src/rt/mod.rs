@@ -32,19 +32,39 @@
 //! Because the OS is not involved in this cooperative multitasking, a `runtime`
 //! is required to ensure each task is scheduled and polled to make progress.
 
+use std::cell::Cell;
+
+mod builder;
+mod handle;
+mod join;
 mod runtime;
 mod spawn;
+mod static_runtime;
 
+pub use builder::Builder;
+pub use handle::Handle;
+pub use join::{JoinError, JoinHandle};
 pub use runtime::Runtime;
-pub use spawn::spawn;
+pub use spawn::{spawn, spawn_blocking};
+pub use static_runtime::StaticRuntime;
 
 #[cfg(all(feature = "io", not(target_os = "linux")))]
 compile_error!("The `io` feature is only compatible with Linux systems that support epoll(7).");
 
 #[cfg(feature = "io")]
-pub(crate) mod io;
+pub mod io;
 
+pub(crate) mod blocking;
 pub(crate) mod scheduler;
 pub(crate) mod task;
 pub(crate) mod timer;
-pub(crate) mod util;
+
+thread_local! {
+    /// Raw pointer to the `Runtime` currently entered on this thread, set by
+    /// `Runtime::block_on`'s `EnterGuard` for the duration of the call.
+    ///
+    /// Used by `Runtime::current()` and by code (e.g. `time::sleep`) that
+    /// needs to reach the active runtime without a `&Runtime` threaded through
+    /// every call site.
+    pub(crate) static CURRENT_RUNTIME: Cell<Option<*const Runtime>> = const { Cell::new(None) };
+}
@@ -3,17 +3,19 @@ use std::future::Future;
 use std::rc::Rc;
 
 use crate::rt::CURRENT_RUNTIME;
+use crate::rt::builder::Builder;
+use crate::rt::handle::Handle;
 use crate::rt::scheduler::Scheduler;
-use crate::rt::task::Task;
-use crate::rt::waker::TaskWaker;
+use crate::rt::static_runtime::{self, StaticRuntime};
+use crate::rt::task::{Task, TaskId};
 
 /// The `rio` runtime.
 #[derive(Debug, Clone)]
 pub struct Runtime {
     /// The executor responsible for scheduling and polling tasks. Wrapped in an
-    /// `Rc` to allow cloning for each `TaskWaker`, enabling them to reschedule
-    /// their associated `Task`.
-    scheduler: Rc<Scheduler>,
+    /// `Rc` so every spawned `Task` can hold its own clone, letting its
+    /// `TaskWaker` reschedule it without a separate allocation.
+    pub(crate) scheduler: Rc<Scheduler>,
 }
 
 /// Guard used to set the thread-local `Runtime` context during initialization.
@@ -44,6 +46,59 @@ impl Runtime {
         }
     }
 
+    /// Returns a [`Builder`] for configuring a `Runtime` before constructing
+    /// it, e.g. to enable a polling throttle with `Builder::throttle`.
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Returns a cheap, cloneable [`Handle`] to this runtime, letting code
+    /// that only has access to the handle spawn tasks or run a future to
+    /// completion without a `&Runtime` passed through.
+    #[inline]
+    pub fn handle(&self) -> Handle {
+        Handle::new(Rc::clone(&self.scheduler))
+    }
+
+    /// Leaks this runtime's scheduler, returning a [`StaticRuntime`] backed
+    /// by a `&'static Scheduler` instead of an `Rc<Scheduler>`.
+    ///
+    /// This permanently leaks the scheduler's allocation: it is never
+    /// reclaimed for the remainder of the program, and the `Runtime` this
+    /// method consumes cannot be recovered. In exchange, tasks spawned
+    /// through the returned `StaticRuntime` reach their scheduler with no
+    /// refcount bookkeeping at all. Only call this once per scheduler you
+    /// intend to use for the process's whole lifetime (e.g. a server's or
+    /// daemon's single runtime), not per-request or in a loop.
+    #[inline]
+    pub fn leak(self) -> &'static StaticRuntime {
+        Box::leak(Box::new(StaticRuntime::new(static_runtime::leak_scheduler(
+            self.scheduler,
+        ))))
+    }
+
+    /// Returns the `Runtime` currently entered on this thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a runtime context (i.e., outside of a
+    /// `Runtime::block_on` call).
+    pub(crate) fn current() -> Runtime {
+        CURRENT_RUNTIME.with(|c| {
+            let ptr = c
+                .get()
+                .expect("there is no reactor running, must be called from the context of a `rio` runtime");
+
+            // SAFETY: The thread-local holds a raw pointer to the `Runtime`
+            // passed to `EnterGuard::new`, which is only set for the duration
+            // of `Runtime::block_on` and cleared when its `EnterGuard` is
+            // dropped. Since we are inside that window, the pointee is alive
+            // and valid for reads.
+            unsafe { (*ptr).clone() }
+        })
+    }
+
     /// Runs the provided `Future` to completion, serving as the runtimeâ€™s entry
     /// point.
     ///
@@ -56,26 +111,31 @@ impl Runtime {
         let output = Rc::new(RefCell::new(None));
         let out_clone = Rc::clone(&output);
 
-        let task = Rc::new(RefCell::new(Task::new(async move {
-            *out_clone.borrow_mut() = Some(future.await);
-        })));
+        let task = Task::new(
+            async move {
+                *out_clone.borrow_mut() = Some(future.await);
+            },
+            Rc::clone(&self.scheduler),
+        );
 
-        let waker = TaskWaker::new(Rc::clone(&task), Rc::clone(&self.scheduler));
+        self.scheduler.block_on_task(task);
 
-        self.scheduler.block_on_task(task, waker);
-
-        output
+        let output = output
             .borrow_mut()
             .take()
-            .expect("`block_on` must produce the provided future's output")
+            .expect("`block_on` must produce the provided future's output");
+        output
     }
 
-    /// Spawns a new asynchronous `Task` on the current `Runtime`.
-    pub(crate) fn spawn_inner<F: Future<Output = ()> + 'static>(&self, future: F) {
-        let task = Rc::new(RefCell::new(Task::new(future)));
-        let waker = TaskWaker::new(Rc::clone(&task), Rc::clone(&self.scheduler));
+    /// Spawns a new asynchronous `Task` on the current `Runtime`, returning
+    /// its `TaskId` so the caller can later cancel it (e.g. `JoinHandle::abort`).
+    pub(crate) fn spawn_inner<F: Future<Output = ()> + 'static>(&self, future: F) -> TaskId {
+        let task = Task::new(future, Rc::clone(&self.scheduler));
+        let id = task.id;
+
+        self.scheduler.spawn_task(task);
 
-        self.scheduler.spawn_task(task, waker);
+        id
     }
 }
 
@@ -1,12 +1,14 @@
-use std::cell::{Cell, RefCell};
+use std::cell::{Cell, OnceCell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::os::unix::io::RawFd;
-use std::task::Context;
-use std::time::Instant;
+use std::rc::Rc;
+use std::task::{Context, Waker};
+use std::time::{Duration, Instant};
 
-use crate::rt::io::Driver;
-use crate::rt::task::{TaskHandle, TaskId, TaskWaker};
-use crate::util::MinHeap;
+use crate::rt::blocking::{BlockingPool, DEFAULT_MAX_BLOCKING_THREADS};
+use crate::rt::io::{Driver, Interest};
+use crate::rt::task::{Runnable, TaskId, TaskWaker};
+use crate::rt::timer::{TimerId, TimerWheel};
 
 thread_local! {
     /// Ensures timers can be associated with the `Task` that was most recently
@@ -16,14 +18,44 @@ thread_local! {
     static CURRENT_TASK: Cell<TaskId> = Cell::new(TaskId::default());
 }
 
-type TaskEntry = (TaskHandle, TaskWaker);
+/// Default cap on how many ready tasks a single `tick` polls before returning
+/// to `block_on_task`'s loop, matching Tokio's own default `event_interval`.
+const DEFAULT_EVENT_INTERVAL: u32 = 61;
+
+/// Configuration a [`Scheduler`] is constructed with, assembled by
+/// [`Builder::build`](crate::rt::Builder::build).
+#[derive(Debug)]
+pub(crate) struct SchedulerConfig {
+    /// See `Builder::throttle`.
+    pub(crate) throttle: Option<Duration>,
+    /// See `Builder::event_interval`.
+    pub(crate) event_interval: u32,
+    /// See `Builder::max_tasks`.
+    pub(crate) capacity_hint: Option<usize>,
+    /// See `Builder::thread_name`.
+    pub(crate) thread_name: Option<Box<str>>,
+    /// See `Builder::max_blocking_threads`.
+    pub(crate) max_blocking_threads: usize,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        SchedulerConfig {
+            throttle: None,
+            event_interval: DEFAULT_EVENT_INTERVAL,
+            capacity_hint: None,
+            thread_name: None,
+            max_blocking_threads: DEFAULT_MAX_BLOCKING_THREADS,
+        }
+    }
+}
 
 /// Single-threaded `Task` scheduler.
 #[derive(Debug)]
 pub(crate) struct Scheduler {
-    /// Stores all live tasks keyed by their ID, each paired with a `TaskWaker`.
-    /// Enables efficient `O(1)` lookup.
-    tasks: RefCell<HashMap<TaskId, TaskEntry>>,
+    /// Stores all live tasks keyed by their ID. Enables efficient `O(1)`
+    /// lookup.
+    tasks: RefCell<HashMap<TaskId, Runnable>>,
     /// Queue of task IDs ready to be polled. Storing only IDs keeps the queue
     /// lightweight and avoids cloning or holding multiple `Task` handles.
     /// `RefCell` allows `TaskWaker`s to have shared mutable access.
@@ -33,77 +65,186 @@ pub(crate) struct Scheduler {
     /// fields mutably, but spawning also requires a mutable borrow. To avoid
     /// double-borrowing during active polling, newly spawned tasks are
     /// temporarily stored here and later transferred on each tick.
-    pending: RefCell<Vec<TaskEntry>>,
-    /// A priority queue of timers associated with tasks, keyed by their
-    /// scheduled wake-up time. Lexicographical ordering is used, meaning
-    /// `wake_at` times are compared first.
-    timers: RefCell<MinHeap<(Instant, TaskId)>>,
+    pending: RefCell<Vec<Runnable>>,
+    /// Hierarchical timing wheel of timers associated with tasks, keyed by
+    /// their scheduled wake-up time.
+    timers: TimerWheel,
     /// Handles the registering and waiting on I/O events, waking tasks when
     /// file descriptors become ready.
     driver: RefCell<Driver>,
+    /// When set, bounds how often the main loop re-enters `epoll_wait` to
+    /// roughly once per interval, batching ready tasks across that window
+    /// instead of returning to the reactor immediately after every tick. See
+    /// `Builder::throttle`.
+    throttle: Option<Duration>,
+    /// Time the last `tick` finished. Only updated and consulted when
+    /// `throttle` is set.
+    last_tick: Cell<Instant>,
+    /// Maximum number of ready tasks polled per `tick` before returning
+    /// control to `block_on_task`'s loop, bounding how long a flood of
+    /// runnable tasks can delay the next `epoll_wait`. See
+    /// `Builder::event_interval`.
+    event_interval: u32,
+    /// Name hint applied to worker threads spawned for blocking work (e.g.
+    /// `spawn_blocking`). This scheduler has no background threads of its
+    /// own, so it only stores the hint for that pool to pick up. See
+    /// `Builder::thread_name`.
+    thread_name: Option<Box<str>>,
+    /// Number of worker threads the `spawn_blocking` pool spawns, lazily
+    /// initialized on first use. See `Builder::max_blocking_threads`.
+    max_blocking_threads: usize,
+    /// Pool of OS threads backing `spawn_blocking`, lazily created on first
+    /// use so a `Scheduler` that never calls `spawn_blocking` never pays for
+    /// worker threads or the notify pipe.
+    blocking: OnceCell<BlockingPool>,
 }
 
 impl Scheduler {
-    /// Creates a new `Scheduler`.
+    /// Creates a new `Scheduler` with rio's default configuration.
     #[inline]
     pub(crate) fn new() -> Self {
+        Scheduler::with_config(SchedulerConfig::default())
+    }
+
+    /// Creates a new `Scheduler` from the given `config`, pre-allocating its
+    /// task storage if `config.capacity_hint` is set.
+    pub(crate) fn with_config(config: SchedulerConfig) -> Self {
+        let capacity = config.capacity_hint.unwrap_or(0);
+
         Scheduler {
-            tasks: Default::default(),
-            ready: Default::default(),
+            tasks: RefCell::new(HashMap::with_capacity(capacity)),
+            ready: RefCell::new(VecDeque::with_capacity(capacity)),
             pending: Default::default(),
-            timers: Default::default(),
+            timers: TimerWheel::new(),
             driver: RefCell::new(Driver::new()),
+            throttle: config.throttle,
+            last_tick: Cell::new(Instant::now()),
+            event_interval: config.event_interval,
+            thread_name: config.thread_name,
+            max_blocking_threads: config.max_blocking_threads,
+            blocking: OnceCell::new(),
         }
     }
 
-    /// Schedules the given `TaskHandle` and associated `TaskWaker`, blocking
-    /// the current thread until the underlying `Task` resolves.
-    pub(crate) fn block_on_task(&self, task: TaskHandle, waker: TaskWaker) {
-        let id = task.borrow().id;
+    /// Schedules the given `Runnable`, blocking the current thread until the
+    /// underlying `Task` resolves.
+    pub(crate) fn block_on_task(&self, task: Runnable) {
+        let id = task.id;
 
         self.schedule_task(id);
-        self.tasks.borrow_mut().insert(id, (task, waker));
+        self.tasks.borrow_mut().insert(id, task);
 
         while !self.is_idle() {
+            let timer_timeout = self
+                .timers
+                .earliest_deadline()
+                .and_then(|deadline| deadline.checked_duration_since(Instant::now()));
+
             // Use the closest expiring timer as the `timeout` for the driver.
             //
-            // `-1` indicates the I/O driver should just block.
-            let timeout = self
-                .timers
-                .borrow()
-                .peek()
-                .and_then(|(timer, _)| timer.checked_duration_since(Instant::now()))
-                .map(|duration| duration.as_millis() as i32)
-                .unwrap_or(-1);
+            // `-1` indicates the I/O driver should just block. When a
+            // throttle interval is configured, the timeout is additionally
+            // capped at the next throttle deadline (`last_tick +
+            // throttle_interval`), so the loop periodically returns from
+            // `epoll_wait` to drain the ready queue even if nothing became
+            // ready, bounding the rate of reactor cycles to roughly
+            // `1/interval`.
+            let timeout = match self.throttle {
+                Some(interval) => {
+                    let throttle_timeout = (self.last_tick.get() + interval)
+                        .saturating_duration_since(Instant::now());
 
-            self.driver
-                .borrow_mut()
-                .poll(timeout, |id| self.schedule_task(id));
+                    let timeout = match timer_timeout {
+                        Some(timer_timeout) => timer_timeout.min(throttle_timeout),
+                        None => throttle_timeout,
+                    };
+
+                    timeout.as_millis() as i32
+                }
+                // Tasks left on the ready queue by a `tick` bounded by
+                // `event_interval` must not wait on an I/O event or timer.
+                None if !self.ready.borrow().is_empty() => 0,
+                None => timer_timeout
+                    .map(|duration| duration.as_millis() as i32)
+                    .unwrap_or(-1),
+            };
+
+            self.driver.borrow_mut().poll(timeout);
 
             self.tick();
+
+            if self.throttle.is_some() {
+                self.last_tick.set(Instant::now());
+            }
         }
     }
 
-    /// Schedules the given `TaskHandle` and associated `TaskWaker`, executing
-    /// it concurrently with other tasks.
+    /// Schedules the given `Runnable`, executing it concurrently with other
+    /// tasks.
     #[inline]
-    pub(crate) fn spawn_task(&self, task: TaskHandle, waker: TaskWaker) {
-        self.pending.borrow_mut().push((task, waker));
+    pub(crate) fn spawn_task(&self, task: Runnable) {
+        self.pending.borrow_mut().push(task);
     }
 
     /// Registers a timer with the scheduler, associating it with the currently
     /// polled `Task`.
-    pub(crate) fn register_timer(&self, duration: Instant) {
+    ///
+    /// Returns the [`TimerId`] the timer was registered under, which can be
+    /// passed to [`cancel_timer`](Scheduler::cancel_timer) to unregister it
+    /// before it fires. Returns `None` if `deadline` has already passed, in
+    /// which case the task is scheduled immediately rather than waiting for
+    /// the next `tick`, and there's nothing to cancel.
+    pub(crate) fn register_timer(&self, deadline: Instant) -> Option<TimerId> {
         let task_id = CURRENT_TASK.with(|c| c.get());
-        self.timers.borrow_mut().push((duration, task_id));
+
+        let id = self.timers.insert(deadline, task_id);
+        if id.is_none() {
+            self.schedule_task(task_id);
+        }
+
+        id
     }
 
-    /// Registers the given file descriptor with the I/O driver, associating it
-    /// with the currently polled `Task`.
-    #[allow(dead_code)]
-    pub(crate) fn register_fd(&self, fd: RawFd, events: u32) {
-        let task_id = CURRENT_TASK.with(|c| c.get());
-        self.driver.borrow_mut().register(fd, events, task_id)
+    /// Cancels a previously registered timer before it fires, if it's still
+    /// pending.
+    pub(crate) fn cancel_timer(&self, id: TimerId) {
+        self.timers.cancel(id);
+    }
+
+    /// Registers the given file descriptor with the I/O driver for the
+    /// direction(s) in `events`, waking `waker` once `epoll(7)` reports
+    /// readiness.
+    pub(crate) fn register_fd(&self, fd: RawFd, events: u32, waker: Waker) {
+        self.driver.borrow_mut().register(fd, events, waker)
+    }
+
+    /// Unregisters the given file descriptor from the I/O driver, dropping
+    /// any wakers still associated with it.
+    pub(crate) fn unregister_fd(&self, fd: RawFd) {
+        self.driver.borrow_mut().unregister(fd)
+    }
+
+    /// Returns `true` if the I/O driver has cached readiness for every
+    /// direction in `interest` on the given file descriptor.
+    pub(crate) fn is_fd_ready(&self, fd: RawFd, interest: u32) -> bool {
+        self.driver.borrow().is_ready(fd, interest)
+    }
+
+    /// Clears the I/O driver's cached readiness bit(s) in `interest` for the
+    /// given file descriptor.
+    ///
+    /// Callers MUST drain the fd for that direction to `WouldBlock` first, as
+    /// the driver's registrations are edge-triggered and won't re-report a
+    /// direction's readiness until a new edge arrives.
+    pub(crate) fn clear_fd_ready(&self, fd: RawFd, interest: u32) {
+        self.driver.borrow_mut().clear_ready(fd, interest)
+    }
+
+    /// Removes `waker`'s registration (or any registration waking the same
+    /// task) from the direction(s) in `interest` for the given file
+    /// descriptor, without otherwise touching the fd's registration.
+    pub(crate) fn deregister_waker(&self, fd: RawFd, interest: u32, waker: &Waker) {
+        self.driver.borrow_mut().deregister_waker(fd, interest, waker)
     }
 
     /// Marks the `Task` associated with the provided ID as ready to be polled.
@@ -112,6 +253,67 @@ impl Scheduler {
         self.ready.borrow_mut().push_back(id);
     }
 
+    /// Dispatches `job` to the `spawn_blocking` worker pool, associating it
+    /// with the currently polled `Task` so it can be rescheduled once `job`
+    /// completes.
+    ///
+    /// Lazily starts the pool (and registers its notify pipe with the I/O
+    /// driver) on first use.
+    pub(crate) fn submit_blocking(&self, job: impl FnOnce() + Send + 'static) {
+        let task_id = CURRENT_TASK.with(|c| c.get());
+        self.blocking_pool().submit(task_id, job);
+    }
+
+    /// Returns the lazily-initialized `spawn_blocking` worker pool,
+    /// registering its notify pipe with the I/O driver the first time it is
+    /// created.
+    fn blocking_pool(&self) -> &BlockingPool {
+        self.blocking.get_or_init(|| {
+            let pool = BlockingPool::new(self.max_blocking_threads, self.thread_name.as_deref());
+
+            // No meaningful wake-up logic is needed here: the pipe only
+            // exists to make `epoll_wait` return promptly when a job
+            // completes, and `process_blocking` drains the real completion
+            // state directly rather than relying on this waker firing.
+            self.register_fd(pool.notify_fd(), Interest::Read.events(), Waker::noop().clone());
+
+            pool
+        })
+    }
+
+    /// Reschedules every task whose `spawn_blocking` job has completed since
+    /// the last `tick`.
+    fn process_blocking(&self) {
+        if let Some(pool) = self.blocking.get() {
+            for id in pool.drain_completed() {
+                self.schedule_task(id);
+            }
+        }
+    }
+
+    /// Cancels the task with the given ID, dropping its `Runnable` (and with
+    /// it, its boxed future) without polling it again.
+    ///
+    /// The task may still have a stale ID sitting in the `ready` queue; `tick`
+    /// already tolerates that, since popping an ID with no matching entry in
+    /// `tasks` is just skipped.
+    ///
+    /// Returns `true` if a matching task was found and removed, either from
+    /// the live task map or the pending-spawn queue.
+    pub(crate) fn cancel_task(&self, id: TaskId) -> bool {
+        if self.tasks.borrow_mut().remove(&id).is_some() {
+            return true;
+        }
+
+        let mut pending = self.pending.borrow_mut();
+        if let Some(pos) = pending.iter().position(|task| task.id == id) {
+            pending.remove(pos);
+            return true;
+        }
+
+        false
+    }
+
     /// Returns `true` if the runtime has no remaining tasks to execute, meaning
     /// no currently active tasks and no spawned tasks waiting to be scheduled.
     ///
@@ -120,44 +322,53 @@ impl Scheduler {
         self.tasks.borrow().is_empty() && self.pending.borrow().is_empty()
     }
 
-    /// Polls all currently ready tasks on the `ready` queue, handling any
-    /// pending spawned tasks as well.
+    /// Polls up to `event_interval` currently ready tasks on the `ready`
+    /// queue, handling any pending spawned tasks as well.
+    ///
+    /// Bounding how many tasks a single `tick` polls keeps a flood of
+    /// runnable tasks from starving `epoll_wait`; any tasks left on the
+    /// queue are picked up on the next loop iteration in `block_on_task`.
     fn tick(&self) {
         self.process_pending();
 
         self.process_timers();
 
-        while let Some(id) = self.ready.borrow_mut().pop_front() {
+        self.process_blocking();
+
+        for _ in 0..self.event_interval {
+            let Some(id) = self.ready.borrow_mut().pop_front() else {
+                break;
+            };
+
             // Temporarily remove the task entry from the map.
-            let entry = self.tasks.borrow_mut().remove(&id);
-            let Some((task, waker)) = entry else {
+            let Some(task) = self.tasks.borrow_mut().remove(&id) else {
                 continue;
             };
 
             // Mark as not currently scheduled.
-            task.borrow().scheduled.set(false);
+            task.scheduled.set(false);
 
             // Set the thread-local task ID to the current task's ID. This
             // establishes implicit context for all descendant futures, allowing
             // them to interact with the scheduler (e.g., for waking) without
             // needing to know or pass the task's identity directly.
-            CURRENT_TASK.with(|c| c.set(task.borrow().id));
+            CURRENT_TASK.with(|c| c.set(task.id));
 
+            // Reconstruct the waker from the same allocation backing `task`,
+            // rather than a separately allocated waker cell.
+            let waker = TaskWaker::new(Rc::clone(&task));
             let mut ctx = Context::from_waker(&waker);
-            let poll = {
-                let mut task_ref = task.borrow_mut();
-                task_ref.poll(&mut ctx)
-            };
+            let poll = task.poll(&mut ctx);
 
             // Reset the current task ID after polling.
             CURRENT_TASK.with(|c| c.set(TaskId::default()));
 
             if poll.is_pending() {
-                // Re-insert the (task, waker) for future polling.
-                self.tasks.borrow_mut().insert(id, (task, waker));
+                // Re-insert the task for future polling.
+                self.tasks.borrow_mut().insert(id, task);
             }
 
-            // Drop the `TaskHandle` and `TaskWaker` if `Poll::Ready`...
+            // Drop the `Runnable` if `Poll::Ready`...
         }
     }
 
@@ -166,10 +377,10 @@ impl Scheduler {
     fn process_pending(&self) {
         let mut pending = self.pending.borrow_mut();
 
-        for (task, waker) in pending.drain(..) {
-            let id = task.borrow().id;
+        for task in pending.drain(..) {
+            let id = task.id;
             self.schedule_task(id);
-            self.tasks.borrow_mut().insert(id, (task, waker));
+            self.tasks.borrow_mut().insert(id, task);
         }
     }
 
@@ -178,23 +389,6 @@ impl Scheduler {
     ///
     /// The timers are processed in order of their scheduled wake-up time.
     fn process_timers(&self) {
-        let time_now = Instant::now();
-
-        loop {
-            let entry = self.timers.borrow_mut().pop();
-            let Some((wake_at, id)) = entry else {
-                break;
-            };
-
-            if wake_at <= time_now {
-                self.schedule_task(id);
-            } else {
-                self.timers.borrow_mut().push((wake_at, id));
-                // Since the earliest timeout in the heap hasn't expired, all
-                // other timers are guaranteed not to have expired either, so
-                // early return.
-                break;
-            }
-        }
+        self.timers.process(|id| self.schedule_task(id));
     }
 }
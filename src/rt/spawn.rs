@@ -1,15 +1,30 @@
 use std::future::Future;
 
-use crate::rt::Runtime;
+use crate::rt::Handle;
+use crate::rt::join::JoinHandle;
 
 /// Spawns a new asynchronous task running in the background, enabling it to
 /// execute concurrently with other tasks.
 ///
-/// Returning the output of `future` is currently not supported, so it will be
-/// polled solely for its side effects.
-pub fn spawn<F: Future<Output = ()> + 'static>(future: F) {
-    println!("spawn: spawning new task");
-    // TODO: possible return the ID, waker, etc. to be used in a JoinHandle,
-    // so the tasks output can be awaited.
-    Runtime::current().spawn_inner(future);
+/// Returns a [`JoinHandle`] which can be awaited to obtain `future`'s output
+/// once it resolves. Dropping the handle does not cancel the task.
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + 'static,
+{
+    Handle::current().spawn(future)
+}
+
+/// Runs `f` on a bounded pool of worker threads, for blocking or CPU-bound
+/// work that shouldn't stall the single-threaded scheduler.
+///
+/// Returns a [`JoinHandle`] which can be awaited to obtain `f`'s output once
+/// it resolves. Unlike `spawn`, `f` must be `Send`, since it runs on a
+/// worker thread rather than the thread driving the runtime.
+pub fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + Unpin + 'static,
+    R: Send + 'static,
+{
+    Handle::current().spawn_blocking(f)
 }
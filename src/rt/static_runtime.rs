@@ -0,0 +1,106 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+
+use crate::rt::join::{JoinHandle, JoinState};
+use crate::rt::scheduler::Scheduler;
+use crate::rt::task::Task;
+
+/// A runtime whose scheduler has been intentionally leaked for the remaining
+/// lifetime of the program, obtained via [`Runtime::leak`].
+///
+/// Ordinarily every spawned task holds its own `Rc<Scheduler>` clone, so
+/// waking it touches a (non-atomic) reference count. Since a
+/// `StaticRuntime`'s scheduler is `&'static` instead, tasks spawned through
+/// it just copy that reference, with no count to bump. This trades away
+/// ever being able to drop the runtime (and reclaim its memory) for a
+/// cheaper hot path, and is meant for long-running servers/daemons that only
+/// ever need one runtime for the life of the process.
+///
+/// [`Runtime::leak`]: crate::rt::Runtime::leak
+#[derive(Debug)]
+pub struct StaticRuntime {
+    scheduler: &'static Scheduler,
+}
+
+impl StaticRuntime {
+    /// Wraps the given `'static` scheduler.
+    #[inline]
+    pub(crate) fn new(scheduler: &'static Scheduler) -> Self {
+        StaticRuntime { scheduler }
+    }
+
+    /// Spawns a new asynchronous task running in the background on this
+    /// runtime, returning a [`JoinHandle`] that can be awaited to obtain its
+    /// output.
+    ///
+    /// See the free [`spawn`](crate::rt::spawn) function for the
+    /// general-purpose equivalent.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+    {
+        let state = Rc::new(RefCell::new(JoinState::new()));
+        let join_state = Rc::clone(&state);
+
+        // Wrap `future` so its output, once resolved, is stored into the
+        // shared slot and any joiner waiting on the `JoinHandle` is woken.
+        let task = Task::new(
+            async move {
+                let output = future.await;
+
+                let mut state = join_state.borrow_mut();
+                state.output = Some(output);
+
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            },
+            self.scheduler,
+        );
+        let id = task.id;
+
+        self.scheduler.spawn_task(task);
+
+        JoinHandle::new(id, self.scheduler, state)
+    }
+
+    /// Runs the provided `Future` to completion, blocking the current thread
+    /// until it resolves.
+    ///
+    /// See [`Runtime::block_on`](crate::rt::Runtime::block_on).
+    pub fn block_on<F: Future + 'static>(&self, future: F) -> F::Output {
+        // Used to capture the result of `future`.
+        let output = Rc::new(RefCell::new(None));
+        let out_clone = Rc::clone(&output);
+
+        let task = Task::new(
+            async move {
+                *out_clone.borrow_mut() = Some(future.await);
+            },
+            self.scheduler,
+        );
+
+        self.scheduler.block_on_task(task);
+
+        let output = output
+            .borrow_mut()
+            .take()
+            .expect("`block_on` must produce the provided future's output");
+        output
+    }
+}
+
+/// Leaks `scheduler`'s allocation, returning a `&'static` reference to it.
+///
+/// Used by [`Runtime::leak`](crate::rt::Runtime::leak). The `Rc` is
+/// deliberately never reconstructed via `Rc::from_raw`, so its allocation is
+/// never freed for the remainder of the program.
+pub(crate) fn leak_scheduler(scheduler: Rc<Scheduler>) -> &'static Scheduler {
+    let ptr = Rc::into_raw(scheduler);
+
+    // SAFETY: `ptr` was just produced by `Rc::into_raw` and is valid for
+    // reads for as long as the allocation lives, which, since we never call
+    // `Rc::from_raw` on it again, is the remainder of the program.
+    unsafe { &*ptr }
+}
@@ -1,18 +1,18 @@
 use std::cell::{Cell, RefCell};
 use std::fmt;
 use std::future::Future;
+use std::ops::Deref;
 use std::pin::Pin;
 use std::rc::Rc;
 use std::task::{Context, Poll};
 
+use crate::rt::scheduler::Scheduler;
+
 thread_local! {
     /// Guarantees that each `Task` is assigned a unique ID.
     static NEXT_ID: Cell<u64> = const { Cell::new(0) };
 }
 
-/// Shared handle to a [`Task`] for single-threaded contexts.
-pub(crate) type TaskHandle = Rc<RefCell<Task>>;
-
 /// Unique identifier for a [`Task`].
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
@@ -35,37 +35,95 @@ impl From<u64> for TaskId {
     }
 }
 
+/// Handle a [`Task`] uses to reach its owning [`Scheduler`].
+///
+/// The common case is a refcounted `Rc<Scheduler>`. The `Static` variant is
+/// used by tasks spawned through [`StaticRuntime`], whose scheduler has been
+/// intentionally leaked to a `&'static Scheduler`; reaching it needs no
+/// refcount bookkeeping at all, since there's no count to touch.
+///
+/// [`StaticRuntime`]: crate::rt::StaticRuntime
+#[derive(Clone, Debug)]
+pub(crate) enum SchedulerRef {
+    Rc(Rc<Scheduler>),
+    Static(&'static Scheduler),
+}
+
+impl Deref for SchedulerRef {
+    type Target = Scheduler;
+
+    fn deref(&self) -> &Scheduler {
+        match self {
+            SchedulerRef::Rc(scheduler) => scheduler,
+            SchedulerRef::Static(scheduler) => scheduler,
+        }
+    }
+}
+
+impl From<Rc<Scheduler>> for SchedulerRef {
+    fn from(scheduler: Rc<Scheduler>) -> Self {
+        SchedulerRef::Rc(scheduler)
+    }
+}
+
+impl From<&'static Scheduler> for SchedulerRef {
+    fn from(scheduler: &'static Scheduler) -> Self {
+        SchedulerRef::Static(scheduler)
+    }
+}
+
 /// Lightweight, non-blocking unit of execution, similar to an OS thread, but
 /// rather than being managed by the OS scheduler, it is managed by the
 /// [runtime].
 ///
+/// Jointly allocates the future with the rest of the state a `TaskWaker`
+/// needs (the scheduler to reschedule onto and the already-scheduled flag),
+/// so a spawn costs a single allocation and cloning a task's waker is just an
+/// `Rc` clone instead of a second allocation.
+///
 /// [runtime]: crate::rt
 pub(crate) struct Task {
     /// Used to uniquely identify a task.
     pub(crate) id: TaskId,
-    /// Pinned, heap-allocated, type-erased [`Future`].
-    future: Pin<Box<dyn Future<Output = ()>>>,
+    /// Scheduler this task was spawned onto. Kept alongside the future so a
+    /// `TaskWaker` built from a [`Runnable`] can reschedule the task without
+    /// its own separate clone of the scheduler handle.
+    pub(crate) scheduler: SchedulerRef,
+    /// Pinned, heap-allocated, type-erased [`Future`]. Wrapped in its own
+    /// `RefCell` so a `Runnable` can be read (e.g. by a waker checking
+    /// `scheduled`) while `tick` separately borrows the future to poll it.
+    future: RefCell<Pin<Box<dyn Future<Output = ()>>>>,
     /// Indicates whether the task has already been scheduled for polling. This
     /// avoids re-queuing already scheduled tasks.
     pub(crate) scheduled: Cell<bool>,
 }
 
+/// Shared handle to a [`Task`], jointly allocated with its scheduling state.
+///
+/// Both the `Scheduler`'s task table and every `RawWaker` cloned from a
+/// `TaskWaker` point at the same `Runnable` allocation.
+pub(crate) type Runnable = Rc<Task>;
+
 impl Task {
-    /// Create a new `Task` using the provided future.
+    /// Creates a new [`Runnable`] wrapping `future`, to be scheduled on
+    /// `scheduler`.
     #[inline]
-    pub(crate) fn new<F: Future<Output = ()> + 'static>(future: F) -> Self {
-        Task {
+    pub(crate) fn new<F>(future: F, scheduler: impl Into<SchedulerRef>) -> Runnable
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        Rc::new(Task {
             id: TaskId::new(),
-            future: Box::pin(future),
+            scheduler: scheduler.into(),
+            future: RefCell::new(Box::pin(future)),
             scheduled: Cell::new(false),
-        }
+        })
     }
 
     /// Polls the inner future, returning the [`Poll`] result.
     #[inline]
-    pub(crate) fn poll(&mut self, ctx: &mut Context<'_>) -> Poll<()> {
-        println!("poll (in Task): polling task {:?}", self.id);
-        self.future.as_mut().poll(ctx)
+    pub(crate) fn poll(&self, ctx: &mut Context<'_>) -> Poll<()> {
+        self.future.borrow_mut().as_mut().poll(ctx)
     }
 }
 
@@ -5,7 +5,7 @@
 //! [runtime]: crate::rt
 
 mod core;
-pub(crate) use core::{Task, TaskHandle, TaskId};
+pub(crate) use core::{Runnable, SchedulerRef, Task, TaskId};
 
 mod waker;
 pub(crate) use waker::TaskWaker;
@@ -4,8 +4,7 @@ use std::ops::Deref;
 use std::rc::Rc;
 use std::task::{RawWaker, RawWakerVTable, Waker};
 
-use crate::rt::scheduler::Scheduler;
-use crate::rt::task::TaskHandle;
+use crate::rt::task::{Runnable, Task};
 
 /// Wrapper around [`Waker`] that enforces `!Send` and `!Sync`.
 #[derive(Debug)]
@@ -16,33 +15,21 @@ pub(crate) struct TaskWaker {
     _marker: PhantomData<Rc<()>>,
 }
 
-#[derive(Debug)]
-struct WakerData {
-    task: TaskHandle,
-    scheduler: Rc<Scheduler>,
-}
-
 impl TaskWaker {
-    /// Creates a new `TaskWaker` using the provided [`TaskHandle`] and
-    /// [`Scheduler`].
-    pub(crate) fn new(task: TaskHandle, scheduler: Rc<Scheduler>) -> Self {
-        let waker_data = Rc::new(WakerData { task, scheduler });
-
+    /// Creates a new `TaskWaker` over the provided [`Runnable`].
+    ///
+    /// The `RawWaker` points directly at `task`'s allocation, so cloning the
+    /// resulting `Waker` is just an `Rc` clone rather than a second
+    /// allocation.
+    pub(crate) fn new(task: Runnable) -> Self {
         TaskWaker {
             // SAFETY: `TaskWaker` wrapper guarantees it is only usable in a
             // single-threaded context. The vtable functions are only ever
             // called with a valid pointer to the associated underlying `Task`.
-            waker: unsafe { Waker::from_raw(Self::raw_waker(waker_data)) },
+            waker: unsafe { Waker::from_raw(raw_waker(task)) },
             _marker: PhantomData,
         }
     }
-
-    fn raw_waker(data: Rc<WakerData>) -> RawWaker {
-        // Does not decrement the reference-count of `WakerData`.
-        let ptr = Rc::into_raw(data) as *const ();
-
-        RawWaker::new(ptr, &WAKER_VTABLE)
-    }
 }
 
 impl Deref for TaskWaker {
@@ -55,63 +42,62 @@ impl Deref for TaskWaker {
 
 const WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
 
+/// Returns a `RawWaker`, consuming `task` without decrementing its
+/// reference-count.
+fn raw_waker(task: Runnable) -> RawWaker {
+    // Does not decrement the reference-count of `Task`.
+    let ptr = Rc::into_raw(task) as *const ();
+
+    RawWaker::new(ptr, &WAKER_VTABLE)
+}
+
 /// Returns a `RawWaker`, incrementing the reference-count of the underlying
-/// `Rc<WakerData>`.
+/// `Runnable`.
 unsafe fn clone(ptr: *const ()) -> RawWaker {
-    // SAFETY: Raw pointer was initially created from a valid `Rc<WakerData>`.
-    let data: Rc<WakerData> = unsafe { Rc::from_raw(ptr as *const WakerData) };
-    let cloned = Rc::clone(&data);
-
-    println!("clone: cloning task waker");
+    // SAFETY: Raw pointer was initially created from a valid `Runnable`.
+    let task: Runnable = unsafe { Rc::from_raw(ptr as *const Task) };
+    let cloned = Rc::clone(&task);
 
-    // Prevent `data` from being dropped, which would incorrectly decrement the
-    // reference-count.
-    mem::forget(data);
+    // Prevent `task` from being dropped, which would incorrectly decrement
+    // the reference-count.
+    mem::forget(task);
 
-    TaskWaker::raw_waker(cloned)
+    raw_waker(cloned)
 }
 
-/// Wakes the underlying `Task`, consuming the `Rc<WakerData>`.
+/// Wakes the underlying `Task`, consuming the `Runnable`.
 unsafe fn wake(ptr: *const ()) {
-    // SAFETY: Raw pointer was initially created from a valid `Rc<WakerData>`.
-    let data: Rc<WakerData> = unsafe { Rc::from_raw(ptr as *const WakerData) };
-
-    // Schedule the underlying task for polling
-    if !data.task.borrow().scheduled.get() {
-        let id = data.task.borrow().id;
-        data.scheduler.schedule_task(id);
+    // SAFETY: Raw pointer was initially created from a valid `Runnable`.
+    let task: Runnable = unsafe { Rc::from_raw(ptr as *const Task) };
 
-        println!("wake: waking task {:?}", id);
+    schedule(&task);
 
-        // Mark task as scheduled.
-        data.task.borrow().scheduled.set(true)
-    }
-
-    // `data` is dropped here, as waking by value should consume the `Waker`.
+    // `task` is dropped here, as waking by value should consume the `Waker`.
 }
 
-/// Wakes the underlying `Task` without consuming the `Rc<WakerData>`.
+/// Wakes the underlying `Task` without consuming the `Runnable`.
 unsafe fn wake_by_ref(ptr: *const ()) {
-    // SAFETY: Raw pointer was initially created from a valid `Rc<WakerData>`.
-    let data: Rc<WakerData> = unsafe { Rc::from_raw(ptr as *const WakerData) };
+    // SAFETY: Raw pointer was initially created from a valid `Runnable`.
+    let task: Runnable = unsafe { Rc::from_raw(ptr as *const Task) };
 
-    // Schedule the underlying task for polling
-    if !data.task.borrow().scheduled.get() {
-        let id = data.task.borrow().id;
-        data.scheduler.schedule_task(id);
-
-        println!("wake_by_ref: waking task {:?}", id);
-
-        // Mark task as scheduled.
-        data.task.borrow().scheduled.set(true)
-    }
+    schedule(&task);
 
     // Waking by reference should not consume the `Waker`.
-    mem::forget(data);
+    mem::forget(task);
 }
 
-/// Drops the `Rc` corresponding to the underlying `WakerData`.
+/// Drops the `Rc` corresponding to the underlying `Runnable`.
 unsafe fn drop(ptr: *const ()) {
-    // SAFETY: Raw pointer was initially created from a valid `Rc<WakerData>`.
-    let _: Rc<WakerData> = unsafe { Rc::from_raw(ptr as *const WakerData) };
+    // SAFETY: Raw pointer was initially created from a valid `Runnable`.
+    let _: Runnable = unsafe { Rc::from_raw(ptr as *const Task) };
+}
+
+/// Schedules `task` for polling if it isn't already queued.
+fn schedule(task: &Runnable) {
+    if !task.scheduled.get() {
+        task.scheduler.schedule_task(task.id);
+
+        // Mark task as scheduled.
+        task.scheduled.set(true);
+    }
 }
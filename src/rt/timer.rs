@@ -1,37 +1,445 @@
-use std::cmp::Ordering;
-use std::task::Waker;
-use std::time::Instant;
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
 
-/// Wrapper for a deadline and associated [`Waker`].
+use crate::rt::task::TaskId;
+
+/// Number of wheel levels. Level 0 holds entries whose remaining ticks fall
+/// in `[0, SLOTS)` and level `L > 0` those in `[SLOTS.pow(L),
+/// SLOTS.pow(L + 1))`, so six levels of 64 slots address
+/// delays from 1ms (level 0) up to roughly 64^6 ms.
+const LEVELS: usize = 6;
+/// Slots per level.
+const SLOTS: usize = 64;
+/// `log2(SLOTS)`, used to shift between a level's tick index and its slot.
+const SLOT_BITS: u32 = 6;
+const SLOT_MASK: u64 = (SLOTS as u64) - 1;
+/// Duration a single level-0 tick represents.
+const TICK: Duration = Duration::from_millis(1);
+
+/// Opaque handle to a timer inserted into a [`TimerWheel`], returned by
+/// [`TimerWheel::insert`] so the caller can later [`TimerWheel::cancel`] it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TimerId(usize);
+
+/// Cached answer to "what's the earliest pending deadline", maintained
+/// incrementally instead of rescanning the slab on every lookup.
+#[derive(Debug, Clone, Copy)]
+enum Earliest {
+    /// No pending timers.
+    Empty,
+    /// The slab index and deadline of the currently-known earliest entry.
+    Known(usize, Instant),
+    /// The previously cached earliest entry fired or was cancelled; the true
+    /// minimum is unknown until the next [`TimerWheel::earliest_deadline`]
+    /// call rescans the slab to find it.
+    Stale,
+}
+
+/// An entry in the wheel's intrusive slot lists.
+///
+/// `prev`/`next` link the entry within whichever slot it currently occupies,
+/// identified by `position` (`(level, slot)`), allowing both cascading and
+/// cancellation to splice an entry out in `O(1)` without scanning the list.
 #[derive(Debug)]
-pub struct TimerEntry {
-    /// When the timer is set to expire.
-    pub(crate) deadline: Instant,
-    /// The associated waker to wake when the timer expires.
-    pub(crate) waker: Waker,
+struct Entry {
+    deadline: Instant,
+    task: TaskId,
+    position: (usize, usize),
+    prev: Option<usize>,
+    next: Option<usize>,
 }
 
-/*
-* Need to manually implement `Ord` since `Waker` does not implement `Ord` and
-* we are only concerned with comparing deadlines.
-*/
+/// A hashed hierarchical timing wheel used to schedule per-task wake-ups.
+///
+/// Unlike a binary heap, insertion only touches the head of a single slot's
+/// doubly-linked list and cancellation splices the entry out of its list in
+/// place, making both `O(1)`. Finding expired
+/// timers amortizes to `O(1)` as well: advancing the clock processes only
+/// level 0's current slot, cascading a higher level's slot down into lower
+/// levels whenever the level below it wraps.
+///
+/// Entries are stored in a slab (`entries`, indexed by [`TimerId`]) so that
+/// relinking an entry between slots on cascade never reallocates.
+#[derive(Debug)]
+pub(crate) struct TimerWheel {
+    entries: RefCell<Vec<Option<Entry>>>,
+    free: RefCell<Vec<usize>>,
+    slots: RefCell<Vec<[Option<usize>; SLOTS]>>,
+    /// Reference point `deadline`s are measured from, in level-0 ticks.
+    base: Instant,
+    /// Number of level-0 ticks already processed since `base`.
+    current_tick: Cell<u64>,
+    /// Count of live (unfired, uncancelled) entries.
+    len: Cell<usize>,
+    /// Cached earliest pending deadline, so `earliest_deadline` doesn't have
+    /// to rescan the whole slab on every call. See [`Earliest`].
+    earliest: Cell<Earliest>,
+}
 
-impl Ord for TimerEntry {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.deadline.cmp(&other.deadline)
+impl TimerWheel {
+    /// Creates an empty `TimerWheel`, anchored to the current time.
+    pub(crate) fn new() -> Self {
+        TimerWheel {
+            entries: RefCell::new(Vec::new()),
+            free: RefCell::new(Vec::new()),
+            slots: RefCell::new(vec![[None; SLOTS]; LEVELS]),
+            base: Instant::now(),
+            current_tick: Cell::new(0),
+            len: Cell::new(0),
+            earliest: Cell::new(Earliest::Empty),
+        }
     }
-}
 
-impl PartialOrd for TimerEntry {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Returns `true` if the wheel holds no pending timers.
+    #[inline]
+    #[allow(unused)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len.get() == 0
     }
-}
 
-impl PartialEq for TimerEntry {
-    fn eq(&self, other: &Self) -> bool {
-        self.deadline == other.deadline
+    /// Returns the nearest deadline still pending, or `None` if the wheel is
+    /// empty.
+    ///
+    /// Used by the scheduler to bound how long it blocks in `epoll_wait`
+    /// before it next needs to drain expired timers. Cheap to call every
+    /// loop iteration: the result is cached and only rescans the slab when
+    /// the previously cached entry has fired or been cancelled.
+    pub(crate) fn earliest_deadline(&self) -> Option<Instant> {
+        match self.earliest.get() {
+            Earliest::Empty => None,
+            Earliest::Known(_, deadline) => Some(deadline),
+            Earliest::Stale => {
+                let found = self
+                    .entries
+                    .borrow()
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, e)| e.as_ref().map(|e| (idx, e.deadline)))
+                    .min_by_key(|&(_, deadline)| deadline);
+
+                self.earliest.set(match found {
+                    Some((idx, deadline)) => Earliest::Known(idx, deadline),
+                    None => Earliest::Empty,
+                });
+
+                found.map(|(_, deadline)| deadline)
+            }
+        }
+    }
+
+    /// Updates the cached earliest deadline to account for a newly inserted
+    /// entry, if the cache is currently trustworthy.
+    ///
+    /// If the cache is [`Earliest::Stale`], the true minimum is already
+    /// unknown and may be smaller than `deadline`, so it's left alone rather
+    /// than overwritten; the next `earliest_deadline` call rescans instead.
+    fn consider(&self, idx: usize, deadline: Instant) {
+        let update = match self.earliest.get() {
+            Earliest::Empty => true,
+            Earliest::Known(_, cached) => deadline < cached,
+            Earliest::Stale => false,
+        };
+
+        if update {
+            self.earliest.set(Earliest::Known(idx, deadline));
+        }
+    }
+
+    /// Invalidates the cached earliest deadline if it was pointing at `idx`,
+    /// since that entry is about to be removed (cancelled or fired).
+    fn invalidate(&self, idx: usize) {
+        if let Earliest::Known(cached_idx, _) = self.earliest.get() {
+            if cached_idx == idx {
+                self.earliest.set(if self.len.get() <= 1 {
+                    Earliest::Empty
+                } else {
+                    Earliest::Stale
+                });
+            }
+        }
+    }
+
+    /// Schedules `task` to be woken at `deadline`.
+    ///
+    /// Returns `None` if `deadline` has already passed, in which case the
+    /// caller should schedule `task` immediately rather than waiting for the
+    /// next `process`. Otherwise returns a [`TimerId`] that can be passed to
+    /// [`cancel`](TimerWheel::cancel).
+    pub(crate) fn insert(&self, deadline: Instant, task: TaskId) -> Option<TimerId> {
+        if deadline <= Instant::now() {
+            return None;
+        }
+
+        let id = self.alloc(Entry {
+            deadline,
+            task,
+            position: (0, 0),
+            prev: None,
+            next: None,
+        });
+
+        self.link(id, self.tick_of(deadline));
+        self.len.set(self.len.get() + 1);
+        self.consider(id, deadline);
+
+        Some(TimerId(id))
+    }
+
+    /// Removes `id`'s timer before it fires, if it's still pending.
+    pub(crate) fn cancel(&self, id: TimerId) {
+        let idx = id.0;
+
+        if self.entries.borrow()[idx].is_none() {
+            return;
+        }
+
+        self.invalidate(idx);
+        self.unlink(idx);
+        self.entries.borrow_mut()[idx] = None;
+        self.free.borrow_mut().push(idx);
+        self.len.set(self.len.get() - 1);
+    }
+
+    /// Advances the wheel to the current time, calling `fire` once for every
+    /// task whose timer has expired, in order of expiration.
+    pub(crate) fn process(&self, mut fire: impl FnMut(TaskId)) {
+        let target = self.tick_of(Instant::now());
+
+        while self.current_tick.get() < target {
+            self.tick(&mut fire);
+        }
+    }
+
+    /// Converts `deadline` into an absolute level-0 tick count relative to
+    /// `base`.
+    fn tick_of(&self, deadline: Instant) -> u64 {
+        let elapsed = deadline.saturating_duration_since(self.base);
+        (elapsed.as_nanos() / TICK.as_nanos()) as u64
+    }
+
+    /// Reserves a slab slot for `entry`, reusing a freed one if available.
+    fn alloc(&self, entry: Entry) -> usize {
+        if let Some(idx) = self.free.borrow_mut().pop() {
+            self.entries.borrow_mut()[idx] = Some(entry);
+            idx
+        } else {
+            let mut entries = self.entries.borrow_mut();
+            entries.push(Some(entry));
+            entries.len() - 1
+        }
+    }
+
+    /// Chooses the coarsest level whose full range still covers `remaining`
+    /// ticks: level 0 covers `[0, 64)`, level 1 covers `[64, 64^2)`, and so
+    /// on, matching the 1ms/64ms/4096ms/... slot granularities per level.
+    fn level_for(remaining: u64) -> usize {
+        let mut range = SLOTS as u64;
+
+        for level in 0..LEVELS - 1 {
+            if remaining < range {
+                return level;
+            }
+            range *= SLOTS as u64;
+        }
+
+        LEVELS - 1
+    }
+
+    /// Places the already-allocated entry at `idx` into the slot appropriate
+    /// for `abs_tick`, at the head of that slot's list.
+    fn link(&self, idx: usize, abs_tick: u64) {
+        let remaining = abs_tick.saturating_sub(self.current_tick.get());
+        let level = Self::level_for(remaining);
+        let slot = ((abs_tick >> (level as u32 * SLOT_BITS)) & SLOT_MASK) as usize;
+
+        let mut slots = self.slots.borrow_mut();
+        let mut entries = self.entries.borrow_mut();
+
+        let head = slots[level][slot];
+        if let Some(head) = head {
+            entries[head].as_mut().unwrap().prev = Some(idx);
+        }
+
+        let entry = entries[idx].as_mut().unwrap();
+        entry.position = (level, slot);
+        entry.prev = None;
+        entry.next = head;
+
+        slots[level][slot] = Some(idx);
+    }
+
+    /// Splices the entry at `idx` out of its current slot's list, without
+    /// freeing its slab slot.
+    fn unlink(&self, idx: usize) {
+        let (level, slot, prev, next) = {
+            let entries = self.entries.borrow();
+            let entry = entries[idx].as_ref().unwrap();
+            (entry.position.0, entry.position.1, entry.prev, entry.next)
+        };
+
+        let mut slots = self.slots.borrow_mut();
+        let mut entries = self.entries.borrow_mut();
+
+        match prev {
+            Some(p) => entries[p].as_mut().unwrap().next = next,
+            None => slots[level][slot] = next,
+        }
+
+        if let Some(n) = next {
+            entries[n].as_mut().unwrap().prev = prev;
+        }
+    }
+
+    /// Processes a single level-0 tick: cascading a higher level down first
+    /// if the previous tick wrapped level 0's slot back to zero, then firing
+    /// every entry due this tick.
+    fn tick(&self, fire: &mut impl FnMut(TaskId)) {
+        let current = self.current_tick.get();
+        let slot = (current & SLOT_MASK) as usize;
+
+        if slot == 0 && current != 0 {
+            self.cascade(1);
+        }
+
+        self.fire_slot(slot, fire);
+
+        self.current_tick.set(current + 1);
+    }
+
+    /// Re-inserts every entry in level `level`'s slot for the current tick
+    /// into the level below, cascading further up if that slot is also about
+    /// to wrap.
+    fn cascade(&self, level: usize) {
+        if level >= LEVELS {
+            return;
+        }
+
+        let current = self.current_tick.get();
+        let slot = ((current >> (level as u32 * SLOT_BITS)) & SLOT_MASK) as usize;
+
+        let head = self.slots.borrow_mut()[level][slot].take();
+
+        let mut next = head;
+        while let Some(idx) = next {
+            let (deadline, entry_next) = {
+                let entries = self.entries.borrow();
+                let entry = entries[idx].as_ref().unwrap();
+                (entry.deadline, entry.next)
+            };
+
+            self.link(idx, self.tick_of(deadline));
+            next = entry_next;
+        }
+
+        if slot == 0 {
+            self.cascade(level + 1);
+        }
+    }
+
+    /// Fires and frees every entry in level 0's `slot`.
+    fn fire_slot(&self, slot: usize, fire: &mut impl FnMut(TaskId)) {
+        let head = self.slots.borrow_mut()[0][slot].take();
+
+        let mut next = head;
+        while let Some(idx) = next {
+            self.invalidate(idx);
+
+            let entry = self.entries.borrow_mut()[idx].take().unwrap();
+            self.free.borrow_mut().push(idx);
+            self.len.set(self.len.get() - 1);
+
+            fire(entry.task);
+            next = entry.next;
+        }
     }
 }
 
-impl Eq for TimerEntry {}
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    fn drain(wheel: &TimerWheel) -> Vec<TaskId> {
+        let mut fired = Vec::new();
+        wheel.process(|id| fired.push(id));
+        fired
+    }
+
+    #[test]
+    fn insert_fires_after_deadline() {
+        let wheel = TimerWheel::new();
+
+        wheel.insert(Instant::now() + Duration::from_millis(5), TaskId::from(1));
+        thread::sleep(Duration::from_millis(15));
+
+        assert_eq!(drain(&wheel), vec![TaskId::from(1)]);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn insert_with_past_deadline_returns_none() {
+        let wheel = TimerWheel::new();
+
+        let id = wheel.insert(Instant::now() - Duration::from_millis(1), TaskId::from(1));
+
+        assert!(id.is_none());
+    }
+
+    #[test]
+    fn cancel_prevents_fire() {
+        let wheel = TimerWheel::new();
+
+        let id = wheel
+            .insert(Instant::now() + Duration::from_millis(5), TaskId::from(1))
+            .unwrap();
+        wheel.cancel(id);
+        thread::sleep(Duration::from_millis(15));
+
+        assert!(drain(&wheel).is_empty());
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn earliest_deadline_tracks_minimum_across_cancellation() {
+        let wheel = TimerWheel::new();
+        let now = Instant::now();
+
+        let soon = wheel.insert(now + Duration::from_millis(5), TaskId::from(1)).unwrap();
+        let later = now + Duration::from_millis(50);
+        wheel.insert(later, TaskId::from(2)).unwrap();
+
+        // The earlier of the two pending deadlines is reported.
+        assert!(wheel.earliest_deadline().unwrap() < later);
+
+        // Cancelling the currently-cached earliest entry forces a rescan,
+        // which should now find the later deadline as the new minimum.
+        wheel.cancel(soon);
+        assert_eq!(wheel.earliest_deadline(), Some(later));
+    }
+
+    #[test]
+    fn cascade_fires_a_far_out_deadline() {
+        let wheel = TimerWheel::new();
+
+        // Comfortably past level 0's 64-tick (64ms) range, forcing this
+        // entry into level 1 and back down via `cascade` before it fires.
+        wheel.insert(Instant::now() + Duration::from_millis(80), TaskId::from(1));
+        thread::sleep(Duration::from_millis(95));
+
+        assert_eq!(drain(&wheel), vec![TaskId::from(1)]);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn fires_tasks_in_deadline_order() {
+        let wheel = TimerWheel::new();
+        let now = Instant::now();
+
+        wheel.insert(now + Duration::from_millis(10), TaskId::from(2));
+        wheel.insert(now + Duration::from_millis(2), TaskId::from(1));
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(drain(&wheel), vec![TaskId::from(1), TaskId::from(2)]);
+    }
+}
@@ -0,0 +1,118 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use crate::rt::timer::TimerId;
+use crate::rt::CURRENT_RUNTIME;
+
+/// Creates a new `Interval` that ticks every `period`, starting immediately.
+pub fn interval(period: Duration) -> Interval {
+    Interval {
+        wake_at: Instant::now(),
+        period,
+        registered: false,
+        timer_id: None,
+    }
+}
+
+/// A timer that resolves repeatedly on a fixed `period`, returned by
+/// [`interval`].
+///
+/// If a call to `tick` is delayed (the task fell behind), the missed ticks
+/// are skipped rather than queued up, so the next `tick` fires at the first
+/// scheduled tick time (a whole number of `period`s after the last one) that
+/// is still in the future, instead of replaying a burst of immediately-ready
+/// ticks.
+#[derive(Debug)]
+pub struct Interval {
+    /// Point in time the next tick is due.
+    wake_at: Instant,
+    /// Fixed duration between ticks.
+    period: Duration,
+    /// Indicates whether `wake_at` has been registered with the scheduler.
+    registered: bool,
+    /// Handle to the registered timer, so it can be cancelled if the
+    /// in-flight `Tick` is dropped before it fires.
+    timer_id: Option<TimerId>,
+}
+
+impl Interval {
+    /// Waits for the next tick to complete.
+    pub async fn tick(&mut self) {
+        Tick(self).await
+    }
+}
+
+/// Future returned by `Interval::tick`.
+struct Tick<'a>(&'a mut Interval);
+
+impl Future for Tick<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        // `Tick<'a>(&'a mut Interval)` has no generics of its own and holds
+        // only a plain reference, so it's always `Unpin`.
+        let this = self.get_mut();
+        let now = Instant::now();
+
+        if now >= this.0.wake_at {
+            // Skip any ticks that elapsed while this task wasn't polled,
+            // instead of firing once per missed tick.
+            while this.0.wake_at <= now {
+                this.0.wake_at += this.0.period;
+            }
+
+            this.0.registered = false;
+            // The wheel already drops its own entry once a timer fires, so
+            // there's nothing left to cancel; forget the (possibly stale)
+            // id rather than risk cancelling whatever the slab slot holds
+            // next.
+            this.0.timer_id = None;
+            return Poll::Ready(());
+        }
+
+        if !this.0.registered {
+            this.0.registered = true;
+
+            let wake_at = this.0.wake_at;
+            this.0.timer_id = CURRENT_RUNTIME.with(|rt| {
+                if let Some(ptr) = rt.get() {
+                    // SAFETY: The thread-local holds a raw pointer to a
+                    // `Runtime`. This pointer is only set via the entry point
+                    // `Runtime::block_on`, and cleared when the associated
+                    // `EnterGuard` is dropped. Polling a `Tick` is only
+                    // possible within the context of a runtime.
+                    let rt = unsafe { &*ptr };
+                    rt.scheduler.register_timer(wake_at)
+                } else {
+                    panic!("`interval` called outside of a rutime context");
+                }
+            });
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Tick<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = self.0.timer_id.take() {
+            self.0.registered = false;
+
+            // Past `wake_at` the wheel may already have fired the timer and
+            // reused its slot, see `poll`.
+            if Instant::now() >= self.0.wake_at {
+                return;
+            }
+
+            CURRENT_RUNTIME.with(|rt| {
+                if let Some(ptr) = rt.get() {
+                    // SAFETY: See the comment in `poll`.
+                    let rt = unsafe { &*ptr };
+                    rt.scheduler.cancel_timer(id);
+                }
+            });
+        }
+    }
+}
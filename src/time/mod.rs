@@ -0,0 +1,10 @@
+//! Time-related utilities.
+
+mod sleep;
+pub use sleep::{sleep, sleep_until, Sleep};
+
+mod timeout;
+pub use timeout::{timeout, timeout_at, Timeout};
+
+mod interval;
+pub use interval::{interval, Interval};
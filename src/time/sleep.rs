@@ -3,6 +3,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
+use crate::rt::timer::TimerId;
 use crate::rt::CURRENT_RUNTIME;
 
 /// Waits until `duration` has elapsed.
@@ -27,6 +28,9 @@ pub struct Sleep {
     wake_at: Instant,
     /// Indicates whether the `Sleep` has been registered with the scheduler.
     registered: bool,
+    /// Handle to the registered timer, so it can be cancelled if `Sleep` is
+    /// dropped before it fires.
+    timer_id: Option<TimerId>,
 }
 
 impl Sleep {
@@ -36,6 +40,7 @@ impl Sleep {
         Sleep {
             wake_at: duration,
             registered: false,
+            timer_id: None,
         }
     }
 }
@@ -48,13 +53,19 @@ impl Future for Sleep {
         // `Sleep` future was created. If the current time is `>=` to the
         // `wake_at` time, the timeout has been reached or passed.
         if Instant::now() >= self.wake_at {
+            // The wheel already drops its own entry once a timer fires, so
+            // there's nothing left to cancel; forget the (possibly stale)
+            // id rather than risk cancelling whatever the slab slot holds
+            // next.
+            self.timer_id = None;
             return Poll::Ready(());
         }
 
         if !self.registered {
             self.registered = true;
 
-            CURRENT_RUNTIME.with(|rt| {
+            let wake_at = self.wake_at;
+            self.timer_id = CURRENT_RUNTIME.with(|rt| {
                 if let Some(ptr) = rt.get() {
                     // SAFETY: The thread-local holds a raw pointer to a
                     // `Runtime`. This pointer is only set via the entry point
@@ -62,7 +73,7 @@ impl Future for Sleep {
                     // `EnterGuard` is dropped. Polling a `Sleep` is only
                     // possible within the context of a runtime.
                     let rt = unsafe { &*ptr };
-                    rt.scheduler.register_timer(self.wake_at);
+                    rt.scheduler.register_timer(wake_at)
                 } else {
                     panic!("`sleep/sleep_until` called outside of a rutime context");
                 }
@@ -72,3 +83,23 @@ impl Future for Sleep {
         Poll::Pending
     }
 }
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(id) = self.timer_id.take() {
+            // Past `wake_at` the wheel may already have fired the timer and
+            // reused its slot, see `poll`.
+            if Instant::now() >= self.wake_at {
+                return;
+            }
+
+            CURRENT_RUNTIME.with(|rt| {
+                if let Some(ptr) = rt.get() {
+                    // SAFETY: See the comment in `poll`.
+                    let rt = unsafe { &*ptr };
+                    rt.scheduler.cancel_timer(id);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use std::{error, fmt};
+
+use crate::rt::timer::TimerId;
+use crate::rt::CURRENT_RUNTIME;
+
+/// Waits for `future` to complete, failing with [`Elapsed`] if `duration`
+/// elapses before it does.
+///
+/// The inner future is always polled first on each call, so a future that
+/// becomes ready in the same tick the deadline elapses still resolves with
+/// its own output rather than `Elapsed`.
+pub fn timeout<F: Future>(duration: Duration, future: F) -> Timeout<F> {
+    timeout_at(Instant::now() + duration, future)
+}
+
+/// Waits for `future` to complete, failing with [`Elapsed`] if `deadline`
+/// passes before it does.
+///
+/// Equivalent to [`timeout`], but takes an absolute deadline rather than a
+/// duration relative to now.
+pub fn timeout_at<F: Future>(deadline: Instant, future: F) -> Timeout<F> {
+    Timeout {
+        future,
+        wake_at: deadline,
+        registered: false,
+        timer_id: None,
+    }
+}
+
+/// Error returned by [`timeout`] when the deadline elapses before the wrapped
+/// future resolves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deadline has elapsed")
+    }
+}
+
+impl error::Error for Elapsed {}
+
+/// Future returned by [`timeout`].
+#[derive(Debug)]
+pub struct Timeout<F> {
+    /// Future being raced against `wake_at`.
+    future: F,
+    /// Point in time at which the timeout is considered elapsed.
+    wake_at: Instant,
+    /// Indicates whether the deadline has been registered with the scheduler.
+    registered: bool,
+    /// Handle to the registered timer, so it can be cancelled if `Timeout`
+    /// is dropped before the deadline elapses.
+    timer_id: Option<TimerId>,
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is never moved out of `self` once pinned; `Timeout`
+        // has no other means of moving it.
+        let future = unsafe { self.as_mut().map_unchecked_mut(|timeout| &mut timeout.future) };
+
+        if let Poll::Ready(output) = future.poll(ctx) {
+            return Poll::Ready(Ok(output));
+        }
+
+        if Instant::now() >= self.wake_at {
+            // SAFETY: `timer_id` is never structurally pinned; only `future`
+            // is, which isn't touched here.
+            let this = unsafe { self.as_mut().get_unchecked_mut() };
+            // The wheel already drops its own entry once a timer fires, so
+            // there's nothing left to cancel; forget the (possibly stale)
+            // id rather than risk cancelling whatever the slab slot holds
+            // next.
+            this.timer_id = None;
+            return Poll::Ready(Err(Elapsed(())));
+        }
+
+        if !self.registered {
+            // SAFETY: `registered`/`timer_id` are never structurally pinned;
+            // only `future` is, which isn't touched here.
+            let this = unsafe { self.as_mut().get_unchecked_mut() };
+            this.registered = true;
+
+            let wake_at = this.wake_at;
+            this.timer_id = CURRENT_RUNTIME.with(|rt| {
+                if let Some(ptr) = rt.get() {
+                    // SAFETY: The thread-local holds a raw pointer to a
+                    // `Runtime`. This pointer is only set via the entry point
+                    // `Runtime::block_on`, and cleared when the associated
+                    // `EnterGuard` is dropped. Polling a `Timeout` is only
+                    // possible within the context of a runtime.
+                    let rt = unsafe { &*ptr };
+                    rt.scheduler.register_timer(wake_at)
+                } else {
+                    panic!("`timeout` called outside of a rutime context");
+                }
+            });
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<F> Drop for Timeout<F> {
+    fn drop(&mut self) {
+        if let Some(id) = self.timer_id.take() {
+            // Past `wake_at` the wheel may already have fired the timer and
+            // reused its slot, see `poll`.
+            if Instant::now() >= self.wake_at {
+                return;
+            }
+
+            CURRENT_RUNTIME.with(|rt| {
+                if let Some(ptr) = rt.get() {
+                    // SAFETY: See the comment in `poll`.
+                    let rt = unsafe { &*ptr };
+                    rt.scheduler.cancel_timer(id);
+                }
+            });
+        }
+    }
+}